@@ -0,0 +1,115 @@
+use crate::process;
+use crate::Cmds;
+use std::fs::File;
+use std::io::Write;
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
+use std::sync::mpsc::Sender;
+
+/// An `OutputCapture` is returned by `Screen::capture_output`. While it exists, text written to
+/// the standard output or error of the process, e.g. by `println!` in a dependency, is printed in
+/// windows instead of breaking the layout of the `Screen`. The original descriptors are restored
+/// when it is dropped.
+///
+/// ```ignore
+/// use multi_window_output::{Bridge, Screen};
+///
+/// let mut screen = Screen::new();
+/// let errors = screen.append_down_child(0).unwrap();
+/// let capture = screen.capture_output(Some(0), Some(errors)).unwrap();
+/// let bridge = Bridge::new(screen);
+///
+/// println!("printed in window 0");
+/// eprintln!("printed in the errors window");
+///
+/// // Write to the terminal again
+/// drop(capture);
+/// ```
+#[derive(Debug)]
+pub struct OutputCapture {
+    // Descriptors replaced by pipes, and duplicates of what they were
+    saved: Vec<(RawFd, File)>,
+}
+
+impl Drop for OutputCapture {
+    fn drop(&mut self) {
+        flush();
+        for (fd, original) in &self.saved {
+            // SAFETY: both descriptors are open. Replacing `fd` closes the pipe, so its reader ends
+            unsafe { libc::dup2(original.as_raw_fd(), *fd) };
+        }
+    }
+}
+
+// Convert C return value into a result
+fn check(value: libc::c_int) -> std::io::Result<libc::c_int> {
+    match value {
+        -1 => Err(std::io::Error::last_os_error()),
+        value => Ok(value),
+    }
+}
+
+// Write text buffered by the standard library before the descriptors change
+fn flush() {
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+}
+
+// Duplicate `fd` into a new descriptor that programs started later don't inherit
+pub fn duplicate(fd: RawFd) -> std::io::Result<File> {
+    // SAFETY: `F_DUPFD_CLOEXEC` creates a new descriptor that nothing else owns
+    let fd = check(unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) })?;
+    // SAFETY: `fd` was just created
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+// Size of the terminal `file` refers to, as `(columns, rows)`
+pub fn terminal_size(file: &File) -> std::io::Result<(u16, u16)> {
+    // SAFETY: `winsize` is plain old data
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    // SAFETY: `size` is a valid `winsize` and the descriptor is owned by `file`
+    check(unsafe { libc::ioctl(file.as_raw_fd(), libc::TIOCGWINSZ, &mut size) })?;
+    Ok((size.ws_col, size.ws_row))
+}
+
+// Replace descriptor `fd` with a pipe, whose lines are sent to window with `id` as `cmd`. Returns a
+// duplicate of the original descriptor
+fn redirect(
+    fd: RawFd,
+    cmd: fn() -> Cmds,
+    id: usize,
+    sender: Sender<(Cmds, usize, String)>,
+) -> std::io::Result<File> {
+    let original = duplicate(fd)?;
+    let mut ends = [0; 2];
+    // SAFETY: `ends` has room for the two descriptors
+    check(unsafe { libc::pipe(ends.as_mut_ptr()) })?;
+    // SAFETY: `pipe` returned two new descriptors that nothing else owns
+    let (reader, writer) = unsafe { (File::from_raw_fd(ends[0]), File::from_raw_fd(ends[1])) };
+    // Programs started later must not keep the pipe open
+    // SAFETY: the descriptor is owned by `reader`
+    check(unsafe { libc::fcntl(reader.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) })?;
+    // SAFETY: both descriptors are open, `writer` is closed afterwards so `fd` is the only write end
+    check(unsafe { libc::dup2(writer.as_raw_fd(), fd) })?;
+    process::stream(reader, cmd, id, sender);
+    Ok(original)
+}
+
+// Redirect the standard output and error to the windows with `stdout` and `stderr`
+pub fn capture(
+    stdout: Option<usize>,
+    stderr: Option<usize>,
+    sender: Sender<(Cmds, usize, String)>,
+) -> std::io::Result<OutputCapture> {
+    flush();
+    let mut capture = OutputCapture { saved: vec![] };
+    if let Some(id) = stdout {
+        let original = redirect(libc::STDOUT_FILENO, || Cmds::Println, id, sender.clone())?;
+        capture.saved.push((libc::STDOUT_FILENO, original));
+    }
+    if let Some(id) = stderr {
+        // Restores the standard output if it fails
+        let original = redirect(libc::STDERR_FILENO, || Cmds::Stderr, id, sender)?;
+        capture.saved.push((libc::STDERR_FILENO, original));
+    }
+    Ok(capture)
+}
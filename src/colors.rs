@@ -11,6 +11,16 @@ mod color_test {
         // Change window's color
         screen.set_window_color(0, Color::Yellow).unwrap();
     }
+    #[test]
+    fn extended_colors(){
+        use super::{ansi_color, rgb_color};
+        assert_eq!(ansi_color(1), Color::Red);
+        assert_eq!(ansi_color(12), Color::LightBlue);
+        assert_eq!(ansi_color(196), Color::LightRed);
+        assert_eq!(ansi_color(232), Color::Black);
+        assert_eq!(rgb_color(250, 250, 250), Color::LightWhite);
+        assert_eq!(rgb_color(0, 200, 0), Color::Green);
+    }
 }
 
 /// `Color`s are used to change `Screen`'s and `Screen` windows' colors.
@@ -28,8 +38,9 @@ mod color_test {
 /// // Change window's color
 /// screen.set_window_color(0, Color::Yellow).unwrap();
 /// ```
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
 pub enum Color {
+    #[default]
     Null, Black, Blue, Cyan, 
     Green, LightBlack, LightBlue, LightCyan, 
     LightGreen, LightMagenta, LightRed, LightWhite, 
@@ -58,3 +69,115 @@ pub fn color_code(color: &Color) -> &'static str {
         Color::Null             => "",
     }
 }
+
+// Map color to foreground screen
+pub fn fg_code(color: &Color) -> &'static str {
+    match color {
+        Color::Black            => termion::color::Black.fg_str(),
+        Color::Blue             => termion::color::Blue.fg_str(),
+        Color::Cyan             => termion::color::Cyan.fg_str(),
+        Color::Green            => termion::color::Green.fg_str(),
+        Color::LightBlack       => termion::color::LightBlack.fg_str(),
+        Color::LightBlue        => termion::color::LightBlue.fg_str(),
+        Color::LightCyan        => termion::color::LightCyan.fg_str(),
+        Color::LightGreen       => termion::color::LightGreen.fg_str(),
+        Color::LightMagenta     => termion::color::LightMagenta.fg_str(),
+        Color::LightRed         => termion::color::LightRed.fg_str(),
+        Color::LightWhite       => termion::color::LightWhite.fg_str(),
+        Color::LightYellow      => termion::color::LightYellow.fg_str(),
+        Color::Magenta          => termion::color::Magenta.fg_str(),
+        Color::Red              => termion::color::Red.fg_str(),
+        Color::White            => termion::color::White.fg_str(),
+        Color::Yellow           => termion::color::Yellow.fg_str(),
+        Color::Null             => "",
+    }
+}
+
+// Colors in the order of the ANSI palette, with their usual RGB values
+const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black,          (0, 0, 0)),
+    (Color::Red,            (205, 0, 0)),
+    (Color::Green,          (0, 205, 0)),
+    (Color::Yellow,         (205, 205, 0)),
+    (Color::Blue,           (0, 0, 238)),
+    (Color::Magenta,        (205, 0, 205)),
+    (Color::Cyan,           (0, 205, 205)),
+    (Color::White,          (229, 229, 229)),
+    (Color::LightBlack,     (127, 127, 127)),
+    (Color::LightRed,       (255, 0, 0)),
+    (Color::LightGreen,     (0, 255, 0)),
+    (Color::LightYellow,    (255, 255, 0)),
+    (Color::LightBlue,      (92, 92, 255)),
+    (Color::LightMagenta,   (255, 0, 255)),
+    (Color::LightCyan,      (0, 255, 255)),
+    (Color::LightWhite,     (255, 255, 255)),
+];
+
+// Closest color to an RGB value
+pub fn rgb_color(r: u8, g: u8, b: u8) -> Color {
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+        d(r, pr) + d(g, pg) + d(b, pb)
+    };
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .unwrap()
+        .0
+}
+
+// CSS value of a color, `None` keeps the default one
+pub fn css_color(color: &Color) -> Option<String> {
+    let (_, (r, g, b)) = PALETTE.iter().find(|(palette, _)| palette == color)?;
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+// Map a color of the 256 colors palette to the closest color
+pub fn ansi_color(index: u8) -> Color {
+    match index {
+        0..=15 => PALETTE[index as usize].0,
+        // 6x6x6 color cube
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { value * 40 + 55 };
+            let index = index - 16;
+            rgb_color(level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        // Grayscale ramp
+        232..=255 => {
+            let gray = (index - 232) * 10 + 8;
+            rgb_color(gray, gray, gray)
+        }
+    }
+}
+
+/// A `Style` sets the colors and attributes of text. `Color::Null` keeps the terminal's color.
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub struct Style {
+    pub fg: Color,
+    pub bg: Color,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+}
+
+// Escape codes to switch from any style to `style`
+pub fn style_code(style: &Style) -> String {
+    let mut code = termion::style::Reset.to_string();
+    let attributes = [
+        (style.bold, termion::style::Bold.to_string()),
+        (style.dim, termion::style::Faint.to_string()),
+        (style.italic, termion::style::Italic.to_string()),
+        (style.underline, termion::style::Underline.to_string()),
+        (style.reverse, termion::style::Invert.to_string()),
+    ];
+    for (set, attribute) in attributes {
+        if set {
+            code.push_str(&attribute);
+        }
+    }
+    code.push_str(fg_code(&style.fg));
+    code.push_str(color_code(&style.bg));
+    code
+}
@@ -0,0 +1,140 @@
+use crate::colors::{self, Style};
+use crate::window::Line;
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+    use crate::colors::Color;
+    fn lines() -> Vec<Line> {
+        let red = Style { fg: Color::Red, bold: true, ..Style::default() };
+        let first = "<ok> ".chars().map(|c| (c, Style::default())).chain("fail".chars().map(|c| (c, red)));
+        vec![first.collect(), Line::default()]
+    }
+    #[test]
+    fn text_formats() {
+        assert_eq!(render("Window 0", &lines(), Format::Text), "<ok> fail\n\n");
+        let red = colors::style_code(&Style { fg: Color::Red, bold: true, ..Style::default() });
+        let reset = colors::style_code(&Style::default());
+        assert_eq!(render("Window 0", &lines(), Format::Ansi), format!("<ok> {}fail{}\n\n", red, reset));
+    }
+    #[test]
+    fn html_format() {
+        let html = render("A & B", &lines(), Format::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert!(html.contains("&lt;ok&gt; <span style=\"color: #cd0000; font-weight: bold\">fail</span>\n\n</pre>"));
+    }
+    #[test]
+    fn file_names() {
+        assert_eq!(file_name("Window 1", Format::Html), "Window 1.html");
+        assert_eq!(file_name("../logs/app", Format::Text), ".._logs_app.txt");
+    }
+}
+
+/// Format of the text exported by `Screen::export` and `Screen::export_all_as`.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Format {
+    /// Text without styles.
+    Text,
+    /// Text with the styles as ANSI escape sequences, displayed by `cat` or `less -R`.
+    Ansi,
+    /// Standalone HTML page, with the styles as CSS.
+    Html,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Ansi => "ansi",
+            Format::Html => "html",
+        }
+    }
+}
+
+// Escape the characters with a meaning in HTML
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// CSS declarations of `style`, empty for the default style
+fn css(style: &Style) -> String {
+    let (mut fg, mut bg) = (colors::css_color(&style.fg), colors::css_color(&style.bg));
+    if style.reverse {
+        // Default colors of the page
+        std::mem::swap(&mut fg, &mut bg);
+        fg = fg.or(Some(BACKGROUND.to_string()));
+        bg = bg.or(Some(FOREGROUND.to_string()));
+    }
+    let mut declarations = vec![];
+    if let Some(fg) = fg {
+        declarations.push(format!("color: {}", fg));
+    }
+    if let Some(bg) = bg {
+        declarations.push(format!("background-color: {}", bg));
+    }
+    let attributes = [
+        (style.bold, "font-weight: bold"),
+        (style.dim, "opacity: 0.6"),
+        (style.italic, "font-style: italic"),
+        (style.underline, "text-decoration: underline"),
+    ];
+    for (set, declaration) in attributes {
+        if set {
+            declarations.push(declaration.to_string());
+        }
+    }
+    declarations.join("; ")
+}
+
+const FOREGROUND: &str = "#e5e5e5";
+const BACKGROUND: &str = "#000000";
+
+// Text of `lines` in `format`. `title` is used by HTML pages
+pub fn render(title: &str, lines: &[Line], format: Format) -> String {
+    let mut text = String::new();
+    if format == Format::Html {
+        text.push_str(&format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+             <style>body {{ color: {}; background-color: {}; }}</style>\n</head>\n<body>\n<pre>",
+            escape(title),
+            FOREGROUND,
+            BACKGROUND
+        ));
+    }
+    for line in lines {
+        match format {
+            Format::Text => text.push_str(&line.text()),
+            Format::Ansi => text.push_str(&line.ansi()),
+            Format::Html => {
+                for span in &line.spans {
+                    match css(&span.style).as_str() {
+                        "" => text.push_str(&escape(&span.text)),
+                        css => text.push_str(&format!("<span style=\"{}\">{}</span>", css, escape(&span.text))),
+                    }
+                }
+            }
+        }
+        text.push('\n');
+    }
+    if format == Format::Html {
+        text.push_str("</pre>\n</body>\n</html>\n");
+    }
+    text
+}
+
+// Name of the file of a window called `name`, without path separators
+pub fn file_name(name: &str, format: Format) -> String {
+    let name: String = name.chars().map(|c| if c == '/' || c == '\\' { '_' } else { c }).collect();
+    format!("{}.{}", name, format.extension())
+}
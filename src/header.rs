@@ -0,0 +1,172 @@
+use crate::time;
+use crate::window::clean;
+use std::time::SystemTime;
+
+#[cfg(test)]
+mod header_tests {
+    use super::*;
+    fn context(name: &str) -> Context<'_> {
+        let now = SystemTime::now();
+        Context { name, now, start: now, unread: 2 }
+    }
+    #[test]
+    fn default_header() {
+        let bar = Bar::default();
+        assert_eq!(bar.render(&context("Main"), 16), "Screen: Main    ");
+    }
+    #[test]
+    fn aligned_segments() {
+        let bar = Bar::new()
+            .left(Segment::Text("L".to_string()))
+            .center(Segment::Unread)
+            .right(Segment::Custom(Box::new(|| "R".to_string())));
+        assert_eq!(bar.render(&context(""), 12), "L 2 unread R");
+        assert_eq!(bar.render(&context(""), 3), "L R");
+    }
+    #[test]
+    fn custom_control_characters() {
+        let bar = Bar::new().left(Segment::Custom(Box::new(|| "a\nb\x1b[31mc\x07".to_string())));
+        assert_eq!(bar.render(&context(""), 5), "a bc ");
+    }
+    #[test]
+    fn elapsed_segment() {
+        let bar = Bar::new().right(Segment::Elapsed);
+        let mut context = context("");
+        context.now = context.start + std::time::Duration::from_secs(61);
+        assert_eq!(bar.render(&context, 10), "  00:01:01");
+    }
+}
+
+/// A `Segment` is a piece of text displayed in a `Bar`. Segments are computed every time the
+/// `Screen` is rendered.
+pub enum Segment {
+    /// Static text.
+    Text(String),
+    /// Name of the `Screen`.
+    ScreenName,
+    /// Local wall-clock time as `HH:MM:SS`.
+    Clock,
+    /// Time since the `Screen` was created as `HH:MM:SS`.
+    Elapsed,
+    /// Amount of windows with output that hasn't been seen, i.e. printed while hidden.
+    Unread,
+    /// Text returned by a user-provided closure.
+    Custom(Box<dyn Fn() -> String + Send>),
+}
+
+impl std::fmt::Debug for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Segment::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Segment::ScreenName => write!(f, "ScreenName"),
+            Segment::Clock => write!(f, "Clock"),
+            Segment::Elapsed => write!(f, "Elapsed"),
+            Segment::Unread => write!(f, "Unread"),
+            Segment::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// A `Bar` is a line at the top (header) or bottom (footer) of a `Screen`. It is composed of
+/// left, center and right aligned `Segment`s. When they don't fit, left segments are
+/// displayed over right segments, and right segments over center segments.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Bar, Segment};
+///
+/// let mut screen = Screen::new();
+/// screen.set_header(
+///     Bar::new()
+///         .left(Segment::ScreenName)
+///         .center(Segment::Unread)
+///         .right(Segment::Clock),
+/// );
+/// screen.set_footer(Some(Bar::new().right(Segment::Elapsed)));
+/// ```
+#[derive(Debug)]
+pub struct Bar {
+    left: Vec<Segment>,
+    center: Vec<Segment>,
+    right: Vec<Segment>,
+}
+
+// Values needed to compute the segments
+pub struct Context<'a> {
+    pub name: &'a str,
+    pub now: SystemTime,
+    pub start: SystemTime,
+    pub unread: usize,
+}
+
+impl Bar {
+    /// Create an empty `Bar`.
+    pub fn new() -> Bar {
+        Bar {
+            left: vec![],
+            center: vec![],
+            right: vec![],
+        }
+    }
+    /// Append `segment` to the left side of the `Bar`.
+    pub fn left(mut self, segment: Segment) -> Bar {
+        self.left.push(segment);
+        self
+    }
+    /// Append `segment` to the center of the `Bar`.
+    pub fn center(mut self, segment: Segment) -> Bar {
+        self.center.push(segment);
+        self
+    }
+    /// Append `segment` to the right side of the `Bar`.
+    pub fn right(mut self, segment: Segment) -> Bar {
+        self.right.push(segment);
+        self
+    }
+    fn segment(segment: &Segment, context: &Context) -> String {
+        match segment {
+            Segment::Text(text) => text.clone(),
+            Segment::ScreenName => context.name.to_string(),
+            Segment::Clock => time::format_clock(context.now),
+            Segment::Elapsed => time::format_duration(
+                context.now.duration_since(context.start).unwrap_or_default(),
+            ),
+            Segment::Unread => format!("{} unread", context.unread),
+            Segment::Custom(f) => clean(&f()),
+        }
+    }
+    fn side(segments: &[Segment], context: &Context) -> Vec<char> {
+        segments
+            .iter()
+            .map(|segment| Self::segment(segment, context))
+            .collect::<String>()
+            .chars()
+            .collect()
+    }
+    // Render bar into a line of exactly `width` characters
+    pub fn render(&self, context: &Context, width: usize) -> String {
+        let mut line = vec![' '; width];
+        let center = Self::side(&self.center, context);
+        let start = width.saturating_sub(center.len()) / 2;
+        for (cell, letter) in line.iter_mut().skip(start).zip(center) {
+            *cell = letter;
+        }
+        let right = Self::side(&self.right, context);
+        let start = width.saturating_sub(right.len());
+        for (cell, letter) in line.iter_mut().skip(start).zip(right) {
+            *cell = letter;
+        }
+        for (cell, letter) in line.iter_mut().zip(Self::side(&self.left, context)) {
+            *cell = letter;
+        }
+        line.into_iter().collect()
+    }
+}
+
+impl Default for Bar {
+    /// The default header, `Screen: {name}` on the left.
+    fn default() -> Self {
+        Bar::new()
+            .left(Segment::Text("Screen: ".to_string()))
+            .left(Segment::ScreenName)
+    }
+}
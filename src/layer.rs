@@ -0,0 +1,280 @@
+use crate::colors::{self, Color, Style};
+use crate::routing::path_matches;
+use crate::{Bridge, Cmds};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+#[cfg(test)]
+mod layer_tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+    #[test]
+    fn route_events() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let bridge = Bridge { bridge: tx, hash: (0..6).collect() };
+        let layer = WindowLayer::new(bridge, 0)
+            .span("job", 1)
+            .target("net::*", 2)
+            .name("db", 3)
+            .auto_windows(&[4]);
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "net::tcp", "connected");
+            tracing::warn!(window = "db", rows = 3, "slow query");
+            let request = tracing::info_span!("request", id = 7);
+            request.in_scope(|| {
+                tracing::info!("started");
+                tracing::info_span!("job").in_scope(|| tracing::info!("running"));
+            });
+            drop(request);
+            // The window of the closed span can be used by the next one
+            tracing::info_span!("request").in_scope(|| tracing::info!(window = 5, "done"));
+        });
+
+        let lines: Vec<(usize, String)> = rx
+            .try_iter()
+            .filter(|(cmd, _, _)| matches!(cmd, Cmds::Println))
+            .map(|(_, id, text)| (id, text))
+            .collect();
+        let ids: Vec<usize> = lines.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![2, 3, 4, 1, 5]);
+        let key = colors::style_code(&Style { fg: Color::Cyan, ..Style::default() });
+        let reset = colors::style_code(&Style::default());
+        assert!(lines[1].1.ends_with(&format!(" slow query {}rows{}=3", key, reset)));
+        assert!(lines[2].1.ends_with(&format!(" request{{{}id{}=7}}: started", key, reset)));
+    }
+    #[test]
+    fn events_after_kill() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let bridge = Bridge { bridge: tx, hash: (0..2).collect() };
+        // The `Screen` is gone
+        drop(rx);
+        let layer = WindowLayer::new(bridge, 0).auto_windows(&[1]);
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("dropped");
+            tracing::info_span!("request").in_scope(|| tracing::info!(window = 3, "dropped"));
+        });
+    }
+}
+
+/// A `WindowLayer` is a `tracing_subscriber::Layer` that prints the events of the `tracing` crate
+/// in the windows of a `Bridge`. Requires the `tracing` feature.
+///
+/// The window of an event is chosen by, in order:
+/// - Its `window` field, with a name registered with `WindowLayer::name` or an id.
+/// - Its spans, from the innermost: their `window` field, their name if registered with
+///   `WindowLayer::span`, or the window given to them by `WindowLayer::auto_windows`.
+/// - Its target, if registered with `WindowLayer::target`.
+/// - The fallback window.
+///
+/// Events are printed with their level, their spans and their fields as `key=value`.
+///
+/// ```ignore
+/// use multi_window_output::{Bridge, Screen, WindowLayer};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let mut screen = Screen::new();
+/// let database = screen.append_left_child(0).unwrap();
+/// let spare = screen.append_down_child(0).unwrap();
+///
+/// let layer = WindowLayer::new(Bridge::new(screen), 0)
+///     .name("db", database)
+///     .target("sqlx::*", database)
+///     // Every request gets the spare window while no other request has it
+///     .auto_windows(&[spare]);
+/// tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer)).unwrap();
+///
+/// tracing::info!(window = "db", table = "users", "migrated");
+/// ```
+pub struct WindowLayer {
+    bridge: Bridge,
+    fallback: usize,
+    spans: HashMap<String, usize>,
+    targets: Vec<(String, usize)>,
+    names: HashMap<String, usize>,
+    // Windows not used by any top-level span
+    pool: Mutex<Vec<usize>>,
+}
+
+impl WindowLayer {
+    /// Create a `WindowLayer` that prints every event in window `fallback` of `bridge`, until routes are added.
+    pub fn new(bridge: Bridge, fallback: usize) -> WindowLayer {
+        WindowLayer {
+            bridge,
+            fallback,
+            spans: HashMap::new(),
+            targets: vec![],
+            names: HashMap::new(),
+            pool: Mutex::new(vec![]),
+        }
+    }
+    /// Print the events inside spans called `name` in window with `id`.
+    pub fn span(mut self, name: &str, id: usize) -> Self {
+        self.spans.insert(name.to_string(), id);
+        self
+    }
+    /// Print the events whose target matches `pattern` in window with `id`. A pattern ending with
+    /// `*` matches any target that starts with the rest, e.g. `net::*` matches `net::tcp`.
+    pub fn target(mut self, pattern: &str, id: usize) -> Self {
+        self.targets.push((pattern.to_string(), id));
+        self
+    }
+    /// Print the events with `window = name`, or inside spans with it, in window with `id`.
+    pub fn name(mut self, name: &str, id: usize) -> Self {
+        self.names.insert(name.to_string(), id);
+        self
+    }
+    /// Give every top-level span, such as a request or a job, one of the windows with `ids` for
+    /// its events while it is open. The window is cleared and titled after the span. Top-level
+    /// spans created while all of them are in use are printed like any other span.
+    pub fn auto_windows(self, ids: &[usize]) -> Self {
+        // Give the first ones first
+        *self.pool.lock().unwrap() = ids.iter().rev().copied().collect();
+        self
+    }
+    // Window with registered `name`, or id
+    fn resolve(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied().or_else(|| name.parse().ok())
+    }
+}
+
+// Fields of a span or an event
+#[derive(Default)]
+struct Fields {
+    message: Option<String>,
+    window: Option<String>,
+    pairs: Vec<(String, String)>,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "window" => self.window = Some(value.to_string()),
+            _ => self.record_debug(field, &value),
+        }
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{:?}", value);
+        match field.name() {
+            "message" => self.message = Some(value),
+            "window" => self.window = Some(value),
+            name => self.pairs.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Fields {
+    // Pairs as `key=value`, with styled keys
+    fn render(&self) -> String {
+        let key = colors::style_code(&Style { fg: Color::Cyan, ..Style::default() });
+        let reset = colors::style_code(&Style::default());
+        let pairs: Vec<String> = (self.pairs.iter())
+            .map(|(name, value)| format!("{}{}{}={}", key, name, reset, value))
+            .collect();
+        pairs.join(" ")
+    }
+}
+
+// Window given to a top-level span by `WindowLayer::auto_windows`
+struct AutoWindow(usize);
+
+fn level_style(level: &Level) -> Style {
+    let fg = match *level {
+        Level::ERROR => Color::Red,
+        Level::WARN => Color::Yellow,
+        Level::INFO => Color::Green,
+        Level::DEBUG => Color::Blue,
+        Level::TRACE => Color::LightBlack,
+    };
+    Style { fg, bold: true, ..Style::default() }
+}
+
+impl<S> tracing_subscriber::Layer<S> for WindowLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        if span.parent().is_none() {
+            if let Some(window) = self.pool.lock().unwrap().pop() {
+                let _ = self.bridge.try_send(Cmds::Clear, window, "");
+                self.bridge.set_title_status(window, span.name());
+                extensions.insert(AutoWindow(window));
+            }
+        }
+        extensions.insert(fields);
+    }
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<Fields>() {
+                values.record(fields);
+            }
+        }
+    }
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+
+        let mut window = fields.window.as_deref().and_then(|name| self.resolve(name));
+        let mut spans = vec![];
+        for span in ctx.event_scope(event).into_iter().flatten() {
+            let extensions = span.extensions();
+            let span_fields = extensions.get::<Fields>();
+            window = window
+                .or_else(|| span_fields.and_then(|fields| fields.window.as_deref()).and_then(|name| self.resolve(name)))
+                .or_else(|| self.spans.get(span.name()).copied())
+                .or_else(|| extensions.get::<AutoWindow>().map(|auto| auto.0));
+            spans.push(match span_fields.map(Fields::render) {
+                Some(pairs) if !pairs.is_empty() => format!("{}{{{}}}", span.name(), pairs),
+                _ => span.name().to_string(),
+            });
+        }
+        let window = window
+            .or_else(|| (self.targets.iter())
+                .find(|(pattern, _)| path_matches(pattern, metadata.target()))
+                .map(|(_, id)| *id))
+            .unwrap_or(self.fallback);
+
+        let mut line = format!(
+            "{}{:<5}{}",
+            colors::style_code(&level_style(metadata.level())),
+            metadata.level(),
+            colors::style_code(&Style::default())
+        );
+        // Outermost span first
+        for span in spans.iter().rev() {
+            line.push_str(&format!(" {}:", span));
+        }
+        if let Some(message) = &fields.message {
+            line.push_str(&format!(" {}", message));
+        }
+        let pairs = fields.render();
+        if !pairs.is_empty() {
+            line.push_str(&format!(" {}", pairs));
+        }
+        // Events routed to windows that don't exist go to the fallback window, and events recorded
+        // once the `Screen` is gone are dropped
+        if self.bridge.try_send(Cmds::Println, window, &line) == Err(std::io::ErrorKind::NotFound) {
+            let _ = self.bridge.try_send(Cmds::Println, self.fallback, &line);
+        }
+    }
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            if let Some(auto) = span.extensions().get::<AutoWindow>() {
+                self.pool.lock().unwrap().push(auto.0);
+            }
+        }
+    }
+}
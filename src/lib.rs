@@ -3,13 +3,42 @@
 //! `Screen::println()`, the current terminal screen will be replaced with the output of the calling screen.
 
 mod window;
+mod capture;
 mod colors;
-pub use colors::Color;
+mod export;
+mod header;
+#[cfg(feature = "tracing")]
+mod layer;
+#[cfg(feature = "log")]
+mod logger;
+mod process;
+mod sink;
+mod pty;
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod routing;
+mod time;
+mod utf8;
+mod writer;
+pub use capture::OutputCapture;
+pub use colors::{Color, Style};
+pub use export::Format;
+pub use header::{Bar, Segment};
+#[cfg(feature = "tracing")]
+pub use layer::WindowLayer;
+#[cfg(feature = "log")]
+pub use logger::{Logger, Route};
+pub use process::ProcessHandle;
+pub use sink::FileSink;
+pub use pty::TerminalHandle;
+pub use time::{Clock, SystemClock};
+pub use window::{Align, Capacity, Chart, ChartKind, Column, Line, Pattern, ProgressBar, Span, Timestamps, Wrap};
+pub use writer::WindowWriter;
 use std::io::Write;
 use termion::color;
 use termion::screen::IntoAlternateScreen;
 use termion::terminal_size;
-use window::{Priority, Window};
+use window::{Cell, Filter, Mode, Priority, Search, Window};
+use writer::Target;
 
 // Max amount of windows per screen
 const MAX_WIN: usize = 6;
@@ -19,6 +48,13 @@ const INIT: Option<Window> = None;
 const MAX_WIDTH: usize = 512;
 const MAX_HEIGHT: usize = 254;
 
+// Tests that replace the process' descriptors, or start programs that inherit them, run one at a time
+#[cfg(test)]
+pub(crate) fn lock_fds() -> std::sync::MutexGuard<'static, ()> {
+    static FDS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    FDS.lock().unwrap_or_else(|error| error.into_inner())
+}
+
 #[cfg(test)]
 mod screen_tests {
     use super::*;
@@ -68,6 +104,362 @@ mod screen_tests {
         screen.set_window_color(0, Color::Blue).unwrap();
     }
     #[test]
+    fn hidden_window_space(){
+        let mut screen = Screen::new();
+        let id = screen.append_left_child(0).unwrap();
+        screen.set_window_name(0, "Left").unwrap();
+        screen.set_window_name(id, "Right").unwrap();
+
+        // Hidden window gives its space to its sibling
+        screen.set_visible(id, false).unwrap();
+        screen.output(0, 0, 40, 0, 10);
+        assert_eq!(screen.buffer[9][0].0, 'L');
+        assert_eq!(screen.buffer[9][39].0, ' ');
+
+        // Hidden parent gives its space to its child
+        screen.set_visible(id, true).unwrap();
+        screen.set_visible(0, false).unwrap();
+        screen.output(0, 0, 40, 0, 10);
+        assert_eq!(screen.buffer[9][0].0, 'R');
+
+        // Showing it again restores the layout
+        screen.set_visible(0, true).unwrap();
+        screen.output(0, 0, 40, 0, 10);
+        assert_eq!(screen.buffer[9][0].0, 'L');
+        assert_eq!(screen.buffer[9][20].0, 'R');
+    }
+    #[test]
+    fn unread_windows(){
+        let mut screen = Screen::new();
+        let id = screen.append_down_child(0).unwrap();
+        screen.set_footer(Some(Bar::new().left(Segment::Unread)));
+        screen.set_visible(id, false).unwrap();
+        screen.println(id, "Hidden").unwrap();
+        screen.println(0, "Shown").unwrap();
+        assert_eq!(screen.bar_context().unread, 1);
+        screen.set_visible(id, true).unwrap();
+        assert_eq!(screen.bar_context().unread, 0);
+    }
+    #[test]
+    fn window_capacity(){
+        let mut screen = Screen::new();
+        screen.set_default_capacity(Capacity::Lines(2));
+        let id = screen.append_left_child(0).unwrap();
+        for _ in 0..4 {
+            screen.println(0, "0123").unwrap();
+            screen.println(id, "0123").unwrap();
+        }
+        assert_eq!(screen.window_memory_usage(0), Ok(16));
+        assert_eq!(screen.window_memory_usage(id), Ok(8));
+        screen.set_window_capacity(0, Capacity::Bytes(4)).unwrap();
+        assert_eq!(screen.memory_usage(), 12);
+    }
+    #[test]
+    fn scrollback(){
+        let mut screen = Screen::new();
+        for i in 0..20 {
+            screen.println(0, &i.to_string()).unwrap();
+        }
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, '1');
+        assert_eq!(screen.buffer[0][1].0, '5');
+
+        screen.page_up(0).unwrap();
+        screen.println(0, "20").unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, '1');
+        assert_eq!(screen.buffer[0][1].0, '0');
+        assert_eq!(screen.windows[0].as_ref().unwrap().title(), "Window 0 ID: 0 [+1 new]");
+
+        screen.scroll_to_top(0).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, '0');
+        assert_eq!(screen.buffer[0][1].0, ' ');
+    }
+    #[test]
+    fn ansi_colors(){
+        let mut screen = Screen::new();
+        screen.println(0, "\x1b[32mOK\x1b[0m done").unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0], ('O', Style { fg: Color::Green, ..Style::default() }));
+        assert_eq!(screen.buffer[0][3], ('d', Style::default()));
+    }
+    #[test]
+    fn canvas_lines(){
+        let mut screen = Screen::new();
+        screen.println(0, "log").unwrap();
+        screen.set_line(0, 2, "\x1b[1mup\x1b[0m 3s").unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, ' ');
+        assert_eq!(screen.buffer[2][0], ('u', Style { bold: true, ..Style::default() }));
+        // Lines sent by a `Bridge`
+        screen.apply((Cmds::Replace, 0, "a\nb".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!((screen.buffer[0][0].0, screen.buffer[1][0].0, screen.buffer[2][0].0), ('a', 'b', ' '));
+        screen.clear_window(0).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, ' ');
+    }
+    #[test]
+    fn search_window(){
+        let mut screen = Screen::new();
+        for line in ["warning: unused", "error: missing", "ok"] {
+            screen.println(0, line).unwrap();
+        }
+        assert_eq!(screen.search(0, Pattern::Regex("^(warning|error)".to_string())), Ok(2));
+        assert_eq!(screen.search(0, Pattern::Regex("(".to_string())), Err(std::io::ErrorKind::InvalidInput));
+        assert_eq!(screen.search(1, Pattern::Text("ok".to_string())), Err(std::io::ErrorKind::NotFound));
+        screen.search(0, Pattern::Text("error".to_string())).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[1][0], ('e', screen.search_style));
+        assert_eq!(screen.buffer[1][5], (':', Style::default()));
+        screen.clear_search(0).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[1][0], ('e', Style::default()));
+    }
+    #[test]
+    fn filter_window(){
+        let mut screen = Screen::new();
+        for line in ["error", "ok", "warning"] {
+            screen.println(0, line).unwrap();
+        }
+        assert_eq!(screen.set_filter(0, Some("("), None), Err(std::io::ErrorKind::InvalidInput));
+        screen.apply((Cmds::Filter(Some("error|warning".to_string()), None), 0, "".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!((screen.buffer[0][0].0, screen.buffer[1][0].0), ('e', 'w'));
+        screen.apply((Cmds::FilterEnabled(false), 0, "".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[1][0].0, 'o');
+        screen.set_filter(0, None, None).unwrap();
+        assert!(!screen.windows[0].as_ref().unwrap().title().contains("[filtered]"));
+    }
+    #[test]
+    fn timestamps(){
+        // Time set by the test
+        struct Manual(std::sync::Mutex<std::time::SystemTime>);
+        impl Clock for Manual {
+            fn now(&self) -> std::time::SystemTime {
+                *self.0.lock().unwrap()
+            }
+        }
+        let clock = std::sync::Arc::new(Manual(std::sync::Mutex::new(std::time::UNIX_EPOCH)));
+        let mut screen = Screen::new();
+        screen.set_clock(clock.clone());
+        for (line, secs) in [("a", 1), ("b", 3)] {
+            *clock.0.lock().unwrap() = std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            screen.println(0, line).unwrap();
+        }
+        screen.set_timestamps(0, Timestamps::Relative).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        let row: String = screen.buffer[1].iter().take(11).map(|cell| cell.0).collect();
+        assert_eq!(row, "    3.000 b");
+        screen.set_timestamps(0, Timestamps::Delta).unwrap();
+        screen.output(0, 0, 20, 0, 6);
+        let row: String = screen.buffer[1].iter().take(11).map(|cell| cell.0).collect();
+        assert_eq!(row, "   +2.000 b");
+        assert!(screen.buffer[1][0].1.dim);
+    }
+    #[test]
+    fn capture_stderr(){
+        let _fds = lock_fds();
+        let mut screen = Screen::new();
+        assert_eq!(screen.capture_output(None, Some(1)).unwrap_err(), std::io::ErrorKind::NotFound);
+        let capture = screen.capture_output(None, Some(0)).unwrap();
+        // SAFETY: the buffer is valid for its length
+        unsafe { libc::write(libc::STDERR_FILENO, b"captured\n".as_ptr().cast(), 9) };
+        drop(capture);
+
+        // The line is read in the background
+        let start = std::time::Instant::now();
+        while screen.windows[0].as_ref().unwrap().buffer.iter().len() == 0 {
+            assert!(start.elapsed() < std::time::Duration::from_secs(5));
+            screen.poll();
+            std::thread::yield_now();
+        }
+        assert!(screen.windows[0].as_ref().unwrap().buffer.iter().any(|line| line.text() == "captured"));
+    }
+    #[test]
+    fn file_sinks(){
+        let directory = std::env::temp_dir().join(format!("multi_window_output_sinks_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let (window_log, screen_log) = (directory.join("window.log"), directory.join("screen.log"));
+        let _ = std::fs::remove_file(&window_log);
+        let _ = std::fs::remove_file(&screen_log);
+
+        let mut screen = Screen::new();
+        screen.set_screen_file_sink(Some(FileSink::new(&screen_log))).unwrap();
+        let child = screen.append_left_child(0).unwrap();
+        screen.set_file_sink(child, Some(FileSink::new(&window_log))).unwrap();
+        screen.println(0, "first").unwrap();
+        screen.println(child, "\x1b[1msecond").unwrap();
+        assert_eq!(screen.set_file_sink(child, Some(FileSink::new(&directory))), Err(std::io::ErrorKind::IsADirectory));
+
+        let bold = colors::style_code(&Style { bold: true, ..Style::default() });
+        let reset = colors::style_code(&Style::default());
+        assert_eq!(std::fs::read_to_string(&window_log).unwrap(), format!("{}second{}\n", bold, reset));
+        assert_eq!(
+            std::fs::read_to_string(&screen_log).unwrap(),
+            format!("[Window 0] first\n[Window 1] {}second{}\n", bold, reset)
+        );
+        assert!(screen.file_sink_errors().is_empty());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+    #[test]
+    fn file_sink_over_capacity(){
+        let directory = std::env::temp_dir().join(format!("multi_window_output_evicted_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let log = directory.join("window.log");
+        let _ = std::fs::remove_file(&log);
+
+        let mut screen = Screen::new();
+        screen.set_window_capacity(0, Capacity::Lines(2)).unwrap();
+        screen.set_file_sink(0, Some(FileSink::new(&log))).unwrap();
+        // Lines dropped to fit the capacity in the same print are written too
+        screen.println(0, "a\nb\nc\nd").unwrap();
+        screen.println(0, "e").unwrap();
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "a\nb\nc\nd\ne\n");
+        assert_eq!(screen.export(0, Format::Text), Ok("d\ne\n".to_string()));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+    #[test]
+    fn export_windows(){
+        let mut screen = Screen::new();
+        let child = screen.append_left_child(0).unwrap();
+        screen.set_window_name(child, "Window 0").unwrap();
+        screen.println(0, "\x1b[31mred\x1b[0m").unwrap();
+        screen.print(0, "pending").unwrap();
+        screen.set_line(child, 1, "row").unwrap();
+        assert_eq!(screen.export(0, Format::Text), Ok("red\npending\n".to_string()));
+        assert_eq!(screen.export(child, Format::Text), Ok("\nrow\n".to_string()));
+        assert_eq!(screen.export(2, Format::Text), Err(std::io::ErrorKind::NotFound));
+
+        let directory = std::env::temp_dir().join(format!("multi_window_output_export_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        screen.export_all_as(&directory, Format::Html).unwrap();
+        let html = std::fs::read_to_string(directory.join("Window 0.html")).unwrap();
+        assert!(html.contains("<span style=\"color: #cd0000\">red</span>"));
+        assert!(std::fs::read_to_string(directory.join("Window 0-1.html")).unwrap().contains("row"));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+    #[test]
+    fn export_unique_names(){
+        let mut screen = Screen::new();
+        let second = screen.append_left_child(0).unwrap();
+        let third = screen.append_down_child(0).unwrap();
+        screen.set_window_name(0, "a").unwrap();
+        screen.set_window_name(second, "a").unwrap();
+        screen.set_window_name(third, "a-1").unwrap();
+        for id in [0, second, third] {
+            screen.println(id, &format!("window {}", id)).unwrap();
+        }
+
+        let directory = std::env::temp_dir().join(format!("multi_window_output_names_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        screen.export_all(&directory).unwrap();
+        let mut files: Vec<String> = (std::fs::read_dir(&directory).unwrap())
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["a-1-2.txt", "a-1.txt", "a.txt"]);
+        assert_eq!(std::fs::read_to_string(directory.join("a-1.txt")).unwrap(), "window 1\n");
+        assert_eq!(std::fs::read_to_string(directory.join("a-1-2.txt")).unwrap(), "window 2\n");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+    #[test]
+    fn styled_spans(){
+        let mut screen = Screen::new();
+        let green = Style { fg: Color::Green, ..Style::default() };
+        let dim = Style { dim: true, ..Style::default() };
+        screen.println_styled(0, Line::new().span("OK", green).span(" 1s", dim)).unwrap();
+        screen.apply((Cmds::Styled(Line::from(vec![Span::from("plain")])), 0, "".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][1], ('K', green));
+        assert_eq!(screen.buffer[0][3], ('1', dim));
+        assert_eq!(screen.buffer[1][0], ('p', Style::default()));
+    }
+    #[test]
+    fn progress_window(){
+        let mut screen = Screen::new();
+        screen.set_progress_bar(0, 0, ProgressBar::new("tests").total(10).ascii(true)).unwrap();
+        screen.apply((Cmds::Progress(0, 5), 0, "".to_string()));
+        screen.output(0, 0, 30, 0, 6);
+        let row: String = screen.buffer[0].iter().take(30).map(|cell| cell.0).collect();
+        assert_eq!(row, "tests [#####------] 5/10  50% ");
+        assert_eq!(screen.buffer[0][7].1.fg, Color::Green);
+        assert!(screen.set_progress(1, 0, 1).is_err());
+    }
+    #[test]
+    fn table_window(){
+        let mut screen = Screen::new();
+        screen.set_table(0, vec![Column::new("id"), Column::new("load").align(Align::Right)]).unwrap();
+        screen.set_row(0, "a", &["web", "0.5"]).unwrap();
+        screen.apply((Cmds::Row(vec!["db".to_string(), "12".to_string()]), 0, "b".to_string()));
+        screen.apply((Cmds::Sort(Some(1), true), 0, "".to_string()));
+        screen.output(0, 0, 30, 0, 6);
+        let rows: Vec<String> = (0..3).map(|row| screen.buffer[row].iter().take(11).map(|cell| cell.0).collect()).collect();
+        assert_eq!(rows, vec!["id   load ▼", "db       12", "web     0.5"]);
+        assert!(screen.buffer[0][0].1.bold);
+        screen.apply((Cmds::RemoveRow, 0, "b".to_string()));
+        screen.output(0, 0, 30, 0, 6);
+        assert_eq!(screen.buffer[1][0].0, 'w');
+    }
+    #[test]
+    fn chart_window(){
+        let mut screen = Screen::new();
+        for sample in [1.0, 4.0] {
+            screen.push_sample(0, sample).unwrap();
+        }
+        screen.apply((Cmds::Chart(Chart::new(ChartKind::Bars)), 0, "".to_string()));
+        screen.apply((Cmds::Sample(2.5), 0, "".to_string()));
+        let window = screen.windows[0].as_ref().unwrap();
+        assert_eq!(window.title(), "Window 0 ID: 0 [min 1 max 4 last 2.50]");
+        screen.output(0, 0, 20, 0, 6);
+        let row = |row: usize| -> String { screen.buffer[row].iter().take(4).map(|cell| cell.0).collect() };
+        assert_eq!((row(0), row(1), row(2)), ("   1".to_string(), "   4".to_string(), "2.50".to_string()));
+    }
+    #[test]
+    fn status_panel(){
+        let mut screen = Screen::new();
+        let green = Style { fg: Color::Green, ..Style::default() };
+        screen.set_status(0, "jobs", "3").unwrap();
+        screen.apply((Cmds::StatusValue("ok".to_string(), green), 0, "net/link".to_string()));
+        screen.apply((Cmds::StatusValue("4".to_string(), Style::default()), 0, "jobs".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        let row = |row: usize| -> String { screen.buffer[row].iter().take(10).map(|cell| cell.0).collect() };
+        assert_eq!(vec![row(0), row(1), row(2), row(3)], vec!["jobs    4 ", "          ", "net       ", "  link  ok"]);
+        assert_eq!(screen.buffer[3][8], ('o', green));
+        screen.apply((Cmds::RemoveStatus, 0, "jobs".to_string()));
+        screen.output(0, 0, 20, 0, 6);
+        assert_eq!(screen.buffer[0][0].0, 'n');
+    }
+    #[test]
+    fn spawn_command(){
+        let _fds = lock_fds();
+        let mut screen = Screen::new();
+        screen.set_stderr_style(Style { fg: Color::Red, ..Style::default() });
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2"]);
+        let handle = screen.spawn_in_window(0, command).unwrap();
+        assert!(handle.wait().success());
+        screen.poll();
+
+        let window = screen.windows[0].as_ref().unwrap();
+        assert!(window.title().contains("[exit 0 in "));
+        let styles: Vec<Color> = window.buffer.iter().map(|line| line.spans[0].style.fg).collect();
+        assert_eq!(styles.len(), 2);
+        assert!(styles.contains(&Color::Null) && styles.contains(&Color::Red));
+    }
+    #[test]
+    fn spawn_missing_command(){
+        let _fds = lock_fds();
+        let mut screen = Screen::new();
+        let bridge = Bridge { bridge: screen.sender.clone(), hash: [0].into() };
+        let command = std::process::Command::new("multi_window_output_missing_command");
+        assert_eq!(bridge.spawn_in_window(0, command).unwrap_err(), std::io::ErrorKind::NotFound);
+        screen.poll();
+        assert_eq!(screen.windows[0].as_ref().unwrap().title(), "Window 0 ID: 0");
+    }
+    #[test]
     fn sample_code(){
         // Create a new screen. It will create a `Screen` with single window with id = 0.
         let mut screen = Screen::new();
@@ -129,24 +521,52 @@ pub struct Screen {
     color: Color,
     windows: [Option<Window>; MAX_WIN],
     count: usize,
+    header: Bar,
+    footer: Option<Bar>,
+    start: std::time::SystemTime,
+    clock: std::sync::Arc<dyn Clock>,
+    // Terminal to render to while the standard output is captured
+    tty: Option<std::fs::File>,
+    // File where the lines of every window are written
+    sink: Option<std::sync::Arc<std::sync::Mutex<sink::Sink>>>,
+    capacity: Capacity,
+    stderr_style: Style,
+    search_style: Style,
+    // Messages from `Bridge`s and background threads
+    sender: std::sync::mpsc::Sender<(Cmds, usize, String)>,
+    receiver: std::sync::mpsc::Receiver<(Cmds, usize, String)>,
     // TODO move color value to window object
     // TODO store Rc<Window> instead of and make hash map of Ids
-    buffer: [[(u8, Color); MAX_WIDTH]; MAX_HEIGHT],
+    buffer: Vec<Vec<Cell>>,
 }
 
 impl Screen {
     /// Create a new `Screen` with one window with `id = 0`.
     // TODO allow user so set default color
     pub fn new() -> Screen {
+        let (sender, receiver) = std::sync::mpsc::channel();
         let mut screen = Screen {
             windows: [INIT; MAX_WIN],
             color: Color::Green,
             count: 1,
             name: "Screen".to_string(),
-            buffer: [[(b' ', Color::Null); MAX_WIDTH]; MAX_HEIGHT],
+            header: Bar::default(),
+            footer: None,
+            start: std::time::SystemTime::now(),
+            clock: std::sync::Arc::new(SystemClock),
+            tty: None,
+            sink: None,
+            capacity: Capacity::default(),
+            stderr_style: Style::default(),
+            search_style: Style { fg: Color::Black, bg: Color::Yellow, ..Style::default() },
+            sender,
+            receiver,
+            buffer: vec![vec![(' ', Style::default()); MAX_WIDTH]; MAX_HEIGHT],
         };
         // Append new window
-        screen.windows[0] = Some(Window::new(0));
+        let mut window = Window::new(0);
+        window.start = screen.start;
+        screen.windows[0] = Some(window);
         screen.load();
         screen
     }
@@ -167,47 +587,74 @@ impl Screen {
         Ok(())
     }
     fn load(&mut self) {
-        // Initiate new screen
-        let mut scr = std::io::stdout().into_alternate_screen().unwrap();
-
         // Get dimensions of terminal
-        let (width, height) = terminal_size().unwrap();
+        let (width, height) = match &self.tty {
+            Some(tty) => capture::terminal_size(tty).unwrap(),
+            None => terminal_size().unwrap(),
+        };
 
-        // Print screen name
-        println!(
-            "{}Screen: {}{}{}",
+        // The bars are as wide as the windows
+        let width = (width as usize).min(MAX_WIDTH);
+
+        // Print header
+        let mut frame = format!(
+            "{}{}{}",
             colors::color_code(&self.color),
-            self.name,
-            " ".repeat((width - 8 - self.name.len() as u16) as usize),
+            self.header.render(&self.bar_context(), width),
             color::Bg(color::Reset)
         );
 
-        // Decrease screen height by one and change type
-        let width = width as usize;
-        let height = height as usize - 1;
+        // Decrease screen height by the bars and change type
+        let height = (height as usize - 1 - self.footer.is_some() as usize).min(MAX_HEIGHT);
 
         // Load window buffer content
         self.output(0, 0, width, 0, height);
 
-        // Output contents of buffer to terminal
-        for i in 0..height {
-            for j in 0..width {
-                // If the character has a bg, print it with ith
-                if self.buffer[i][j].1 != Color::Null {
-                    print!(
-                        "{}{}{}",
-                        colors::color_code(&self.buffer[i][j].1),
-                        self.buffer[i][j].0 as char,
-                        color::Bg(color::Reset)
-                    );
-                // Else, print it as a normal chacater
-                } else {
-                    print!("{}", self.buffer[i][j].0 as char);
+        // Output contents of buffer to terminal, only changing style when needed
+        let mut style = Style::default();
+        for row in self.buffer.iter().take(height) {
+            for &(letter, cell_style) in row.iter().take(width) {
+                if cell_style != style {
+                    frame.push_str(&colors::style_code(&cell_style));
+                    style = cell_style;
                 }
+                frame.push(letter);
             }
         }
+        frame.push_str(&colors::style_code(&Style::default()));
+
+        // Print footer
+        if let Some(footer) = &self.footer {
+            frame.push_str(&format!(
+                "{}{}{}",
+                colors::color_code(&self.color),
+                footer.render(&self.bar_context(), width),
+                color::Bg(color::Reset)
+            ));
+        }
+        // Initiate new screen
+        let output: Box<dyn Write> = match &self.tty {
+            Some(tty) => Box::new(tty),
+            None => Box::new(std::io::stdout()),
+        };
+        let mut scr = output.into_alternate_screen().unwrap();
+        write!(scr, "{}", frame).unwrap();
         scr.flush().unwrap();
     }
+    // Values displayed by header and footer segments
+    fn bar_context(&self) -> header::Context<'_> {
+        header::Context {
+            name: &self.name,
+            now: self.clock.now(),
+            start: self.start,
+            unread: self
+                .windows
+                .iter()
+                .flatten()
+                .filter(|window| window.unread > 0)
+                .count(),
+        }
+    }
     fn output(
         &mut self,
         id: usize,
@@ -216,103 +663,99 @@ impl Screen {
         start_height: usize,
         mut end_height: usize,
     ) {
-        // DFS through screens
-        match self.windows[id].as_ref().unwrap().priority {
-            Some(Priority::Vertical) => {
-                // First go to left child
-                let mit = (start_width + end_width) / 2;
-                let left_id: usize = *self.windows[id]
-                    .as_ref()
-                    .unwrap()
-                    .left_child
-                    .as_ref()
-                    .unwrap();
-                self.output(left_id, mit, end_width, start_height, end_height);
-                end_width = mit;
-                // If down child exists, output put that one
-                if let Some(down_id) = self.windows[id].as_ref().unwrap().down_child.as_ref() {
-                    let mit = (start_height + end_height) / 2;
-                    self.output(*down_id, start_width, end_width, mit, end_height);
-                    end_height = mit;
-                }
+        // DFS through screens. Children whose whole subtree is hidden don't take any space
+        let window = self.windows[id].as_ref().unwrap();
+        let children: Vec<(usize, Priority)> = match window.priority {
+            Some(Priority::Vertical) => vec![
+                (window.left_child, Priority::Vertical),
+                (window.down_child, Priority::Horizontal),
+            ],
+            Some(Priority::Horizontal) => vec![
+                (window.down_child, Priority::Horizontal),
+                (window.left_child, Priority::Vertical),
+            ],
+            None => vec![],
+        }
+        .into_iter()
+        .filter_map(|(child, priority)| child.map(|child| (child, priority)))
+        .filter(|(child, _)| self.is_shown(*child))
+        .collect();
+        let visible = window.visible;
+
+        let last = children.len();
+        for (i, (child, priority)) in children.into_iter().enumerate() {
+            // A hidden window gives its own space to its last shown child
+            if !visible && i + 1 == last {
+                self.output(child, start_width, end_width, start_height, end_height);
+                return;
             }
-            Some(Priority::Horizontal) => {
-                // First go to down child
-                let mit = (start_height + end_height) / 2;
-                let down_id: usize = *self.windows[id]
-                    .as_ref()
-                    .unwrap()
-                    .down_child
-                    .as_ref()
-                    .unwrap();
-                self.output(down_id, start_width, end_width, mit, end_height);
-                end_height = mit;
-                // If left child exists, output put that one
-                if let Some(left_id) = self.windows[id].as_ref().unwrap().left_child.as_ref() {
+            match priority {
+                // Left child goes to the right half
+                Priority::Vertical => {
                     let mit = (start_width + end_width) / 2;
-                    self.output(*left_id, mit, end_width, start_height, end_height);
+                    self.output(child, mit, end_width, start_height, end_height);
                     end_width = mit;
                 }
+                // Down child goes to the lower half
+                Priority::Horizontal => {
+                    let mit = (start_height + end_height) / 2;
+                    self.output(child, start_width, end_width, mit, end_height);
+                    end_height = mit;
+                }
             }
-            None => {}
         }
 
-        // TODO keep variable in window that keeps track of available pintable lines
-        let buffer_size = self.windows[id].as_ref().unwrap().buffer.iter().count();
-
-        let mut it = self.windows[id].as_ref().unwrap().buffer.iter();
-        // Check if all printable lines fit inside the window height size, other wise
-        // move the iterator forward so last inserted line will enter in the output
-        if buffer_size > end_height - start_height - 1 {
-            it.nth(buffer_size - (end_height - start_height))
-                .unwrap()
-                .as_ref()
-                .unwrap();
+        // Nothing left to show in this area
+        if !visible {
+            for row in self.buffer.iter_mut().take(end_height).skip(start_height) {
+                for cell in row.iter_mut().take(end_width).skip(start_width) {
+                    *cell = (' ', Style::default());
+                }
+            }
+            return;
         }
 
+        // Show the lines at the scroll position, the last column is left for the border
+        let window = self.windows[id].as_mut().unwrap();
+        window.height = end_height - start_height - 1;
+        window.width = end_width - start_width - 1;
+        let mut it = window.rows().into_iter();
+
         // Dump window buffer into screen buffer for output
         // Default empty line value
-        let empty_line = "-- ".to_string();
+        let empty_line: Vec<Cell> = "-- ".chars().map(|c| (c, Style::default())).collect();
+        let border = Style {
+            bg: self.windows[id].as_ref().unwrap().color,
+            ..Style::default()
+        };
         for i in start_height..end_height - 1 {
             // If there is a line, print its content, otherwise, print `empty_line` value
-            let line = match it.next() {
-                Some(s) => s.as_ref().unwrap(),
-                None => &empty_line,
-            };
-            let mut letter = line.chars();
+            let line = it.next().unwrap_or_else(|| empty_line.clone());
+            let mut letter = line.into_iter();
             // Print letter by letter so content doesnt overflow
             for j in start_width..end_width {
                 if j == end_width - 1 {
-                    self.buffer[i][j] = (b' ', self.windows[id].as_ref().unwrap().color)
+                    self.buffer[i][j] = (' ', border)
                 } else {
-                    self.buffer[i][j] = (
-                        match letter.next() {
-                            Some(l) => l as u8,
-                            None => b' ',
-                        },
-                        Color::Null,
-                    )
+                    self.buffer[i][j] = letter.next().unwrap_or((' ', Style::default()));
                 };
             }
         }
 
         // Load name in the lower part
-        let name = format!(
-            "{} ID: {}",
-            self.windows[id].as_ref().unwrap().get_name(),
-            id
-        );
+        let name = self.windows[id].as_ref().unwrap().title();
         let mut c = name.chars();
         for j in start_width..end_width {
-            self.buffer[end_height - 1][j] = (
-                match c.next() {
-                    Some(l) => l as u8,
-                    None => b' ',
-                },
-                self.windows[id].as_ref().unwrap().color
-            );
+            self.buffer[end_height - 1][j] = (c.next().unwrap_or(' '), border);
         }
     }
+    // Check if window or any window in its subtree is visible
+    fn is_shown(&self, id: usize) -> bool {
+        let window = self.windows[id].as_ref().unwrap();
+        window.visible
+            || window.left_child.is_some_and(|child| self.is_shown(child))
+            || window.down_child.is_some_and(|child| self.is_shown(child))
+    }
     // Validate existance of window
     // TODO make it validation for Rc pointer
     fn validate_id(&self, id: usize) -> Result<(), std::io::ErrorKind> {
@@ -351,7 +794,12 @@ impl Screen {
             return Err(std::io::ErrorKind::AlreadyExists);
         }
 
-        self.windows[self.count] = Some(Window::new(self.count));
+        let mut window = Window::new(self.count);
+        window.set_capacity(self.capacity);
+        window.buffer.set_clock(self.clock.clone());
+        window.start = self.start;
+        window.screen_sink = self.sink.clone();
+        self.windows[self.count] = Some(window);
         match priority {
             Priority::Vertical => self.windows[id].as_mut().unwrap().left_child = Some(self.count),
             Priority::Horizontal => {
@@ -371,6 +819,32 @@ impl Screen {
         self.print(id, line).unwrap();
         self.flush(id)
     }
+    /// Print a `Line` made of differently styled `Span`s in window with `id`. The text of the spans
+    /// is not parsed for ANSI escape sequences. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Color, Line, Screen, Style};
+    ///
+    /// let mut screen = Screen::new();
+    /// let line = Line::new()
+    ///     .span("OK", Style { fg: Color::Green, ..Style::default() })
+    ///     .span(" tests passed ", Style::default())
+    ///     .span("0.4s", Style { dim: true, ..Style::default() });
+    /// screen.println_styled(0, line).unwrap();
+    /// ```
+    pub fn println_styled(&mut self, id: usize, line: Line) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exits
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().println_styled(&line);
+        self.load();
+        Ok(())
+    }
+    /// Create a `WindowWriter` that prints in window with `id`, for code that expects a writer. Returns the `WindowWriter` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn writer(&mut self, id: usize) -> Result<WindowWriter<'_>, std::io::ErrorKind> {
+        // Validate if child exits
+        self.validate_id(id)?;
+        Ok(WindowWriter::new(Target::Screen(self), id))
+    }
     /// Print `line` in window with `id`, but do not flush. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
     pub fn print(&mut self, id: usize, line: &str) -> Result<(), std::io::ErrorKind> {
         // Validate if child exits
@@ -386,6 +860,606 @@ impl Screen {
         self.load();
         Ok(())
     }
+    /// Hide or show window with `id`. A hidden window keeps receiving content, but its space is
+    /// given to the other windows until it is shown again. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_visible(&mut self, id: usize, visible: bool) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let window = self.windows[id].as_mut().unwrap();
+        window.visible = visible;
+        if visible {
+            window.unread = 0;
+        }
+        self.load();
+        Ok(())
+    }
+    /// Scroll window with `id` back by `lines`. New lines don't move the view while the window is
+    /// scrolled back. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn scroll_up(&mut self, id: usize, lines: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().scroll_up(lines);
+        self.load();
+        Ok(())
+    }
+    /// Scroll window with `id` forward by `lines`. Once it reaches the bottom, the window follows new
+    /// lines again. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn scroll_down(&mut self, id: usize, lines: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().scroll_down(lines);
+        self.load();
+        Ok(())
+    }
+    /// Scroll window with `id` back by its height. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn page_up(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        let height = self.windows[id].as_ref().unwrap().height;
+        self.scroll_up(id, height)
+    }
+    /// Scroll window with `id` forward by its height. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn page_down(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        let height = self.windows[id].as_ref().unwrap().height;
+        self.scroll_down(id, height)
+    }
+    /// Scroll window with `id` to its oldest line. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn scroll_to_top(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        self.scroll_up(id, usize::MAX)
+    }
+    /// Scroll window with `id` to its last line, and follow new lines. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn scroll_to_bottom(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        self.scroll_down(id, usize::MAX)
+    }
+    /// Set what window with `id` does with lines longer than its width. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_wrap(&mut self, id: usize, wrap: Wrap) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().wrap = wrap;
+        Ok(())
+    }
+    /// Ignore the styles set by ANSI escape sequences in the text printed to window with `id`. By
+    /// default, colors and attributes such as bold are displayed. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_strip_ansi(&mut self, id: usize, strip: bool) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().buffer.set_strip(strip);
+        Ok(())
+    }
+    /// Write `text` in `row` of window with `id`, replacing what was there. Text after a new line
+    /// character is written in the following rows. The window stops displaying printed lines and
+    /// becomes a canvas, useful for dashboards that update values in place. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::Screen;
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_line(0, 0, "Requests: 0").unwrap();
+    /// screen.set_line(0, 1, "Errors: 0").unwrap();
+    /// // Only the first row changes
+    /// screen.set_line(0, 0, "Requests: 1").unwrap();
+    /// ```
+    pub fn set_line(&mut self, id: usize, row: usize, text: &str) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_line(row, text);
+        self.load();
+        Ok(())
+    }
+    /// Replace all the rows of window with `id` with `lines`, and make it a canvas like `Screen::set_line`.
+    /// Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn replace_contents(&mut self, id: usize, lines: &[&str]) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().replace_contents(lines);
+        self.load();
+        Ok(())
+    }
+    /// Remove all the lines of window with `id`, or all the rows if it is a canvas. The screen of a
+    /// terminal window is blanked, and its program draws over it from then on. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn clear_window(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().clear();
+        self.load();
+        Ok(())
+    }
+    /// Display `progress` in the row of `bar` of window with `id`, replacing the bar that was there.
+    /// The window stops displaying printed lines and shows one progress bar per row. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{ProgressBar, Screen};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_progress_bar(0, 0, ProgressBar::new("compile").total(120)).unwrap();
+    /// screen.set_progress_bar(0, 1, ProgressBar::new("upload").total(2048).unit("kB").ascii(true)).unwrap();
+    /// screen.set_progress(0, 0, 37).unwrap();
+    /// ```
+    pub fn set_progress_bar(&mut self, id: usize, bar: usize, progress: ProgressBar) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_progress_bar(bar, progress);
+        self.load();
+        Ok(())
+    }
+    /// Set the current value of progress bar `bar` of window with `id`. The throughput and the
+    /// estimated time left are measured from the first value set. A bar that wasn't set with
+    /// `Screen::set_progress_bar` is created without a label nor a total. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_progress(&mut self, id: usize, bar: usize, value: u64) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_progress(bar, value);
+        self.load();
+        Ok(())
+    }
+    /// Display a table with `columns` in window with `id`, without rows. The window stops displaying
+    /// printed lines, and shows the rows set with `Screen::set_row` under a header with the titles
+    /// of the columns. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Align, Column, Screen};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_table(0, vec![Column::new("job"), Column::new("state"), Column::new("time").align(Align::Right)]).unwrap();
+    /// screen.set_row(0, "7", &["build", "running", "0.8s"]).unwrap();
+    /// screen.set_row(0, "8", &["lint", "queued", ""]).unwrap();
+    /// screen.set_row(0, "7", &["build", "done", "2.1s"]).unwrap();
+    /// // Longest jobs first
+    /// screen.sort_table(0, Some(2), true).unwrap();
+    /// ```
+    pub fn set_table(&mut self, id: usize, columns: Vec<Column>) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_table(columns);
+        self.load();
+        Ok(())
+    }
+    /// Set the `values` of the row with `key` of the table in window with `id`, updating it in place
+    /// or adding it at the end. Rows with more values than columns get untitled columns. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_row(&mut self, id: usize, key: &str, values: &[&str]) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_row(key, values);
+        self.load();
+        Ok(())
+    }
+    /// Remove the row with `key` of the table in window with `id`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn remove_row(&mut self, id: usize, key: &str) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().remove_row(key);
+        self.load();
+        Ok(())
+    }
+    /// Sort the rows of the table in window with `id` by `column`, comparing values as numbers when
+    /// both are. Numbers go before the other values. `None` displays them in the order they were added. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn sort_table(&mut self, id: usize, column: Option<usize>, descending: bool) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().sort_table(column, descending);
+        self.load();
+        Ok(())
+    }
+    /// Draw the samples of window with `id` with `chart`. The window stops displaying printed lines,
+    /// and keeps the samples it already had if it was a chart. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Chart, ChartKind, Screen};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_chart(0, Chart::new(ChartKind::Bars).capacity(100)).unwrap();
+    /// for latency in [12.0, 15.5, 9.8] {
+    ///     screen.push_sample(0, latency).unwrap();
+    /// }
+    /// ```
+    pub fn set_chart(&mut self, id: usize, chart: Chart) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_chart(chart);
+        self.load();
+        Ok(())
+    }
+    /// Add `sample` to the chart of window with `id`, making it a sparkline if it isn't a chart. The
+    /// oldest sample is dropped once the chart is full. Samples that are not finite numbers are
+    /// ignored. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn push_sample(&mut self, id: usize, sample: f64) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().push_sample(sample);
+        self.load();
+        Ok(())
+    }
+    /// Set the `value` of `key` in the status panel of window with `id`, updating it in place or
+    /// adding it at the end. The window stops displaying printed lines and shows the keys with their
+    /// values in an aligned column. A key like `db/pool` goes in the `db` section, keys without
+    /// section go first. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::Screen;
+    ///
+    /// let mut screen = Screen::new();
+    /// // uptime    3m
+    /// //
+    /// // db
+    /// //   pool    12
+    /// //   errors  0
+    /// screen.set_status(0, "uptime", "3m").unwrap();
+    /// screen.set_status(0, "db/pool", "12").unwrap();
+    /// screen.set_status(0, "db/errors", "0").unwrap();
+    /// ```
+    pub fn set_status(&mut self, id: usize, key: &str, value: &str) -> Result<(), std::io::ErrorKind> {
+        self.set_status_styled(id, key, value, Style::default())
+    }
+    /// Set the `value` of `key` in the status panel of window with `id` like `Screen::set_status`,
+    /// displaying the value with `style`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_status_styled(&mut self, id: usize, key: &str, value: &str, style: Style) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_status_value(key, value, style);
+        self.load();
+        Ok(())
+    }
+    /// Remove `key` from the status panel of window with `id`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn remove_status(&mut self, id: usize, key: &str) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().remove_status_value(key);
+        self.load();
+        Ok(())
+    }
+    /// Run `command` in a pseudo-terminal, and display its screen in window with `id` instead of the
+    /// printed lines. Programs that draw on the whole terminal, such as `top`, are displayed
+    /// correctly, and are told the size of the window every time it changes. Returns a
+    /// `TerminalHandle` to send input, wait for, or kill the program if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// The program's output is read in a background thread. If the `Screen` was passed to a
+    /// `Bridge`, it is displayed automatically. Otherwise, call `Screen::poll` to display it.
+    pub fn spawn_terminal(
+        &mut self,
+        id: usize,
+        command: std::process::Command,
+    ) -> Result<TerminalHandle, std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let window = self.windows[id].as_mut().unwrap();
+        let (session, handle) = pty::spawn(command, window.width, window.height, self.sender.clone())
+            .map_err(|error| error.kind())?;
+        window.mode = Mode::Terminal(session);
+        self.load();
+        Ok(handle)
+    }
+    /// Run `command`, and print its stdout and stderr in window with `id` line by line. Once it
+    /// exits, its exit status and runtime are displayed in the window's title. Returns a
+    /// `ProcessHandle` to wait for or kill the command if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// The output is read in background threads. If the `Screen` was passed to a `Bridge`, it is
+    /// printed automatically. Otherwise, call `Screen::poll` to print it.
+    pub fn spawn_in_window(
+        &mut self,
+        id: usize,
+        command: std::process::Command,
+    ) -> Result<ProcessHandle, std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let handle = process::spawn(command, id, self.sender.clone()).map_err(|error| error.kind())?;
+        self.windows[id].as_mut().unwrap().status = Some("running".to_string());
+        Ok(handle)
+    }
+    /// Print the text written to the standard output of the process in window with id `stdout`,
+    /// and the text written to its standard error in window with id `stderr`, e.g. by `println!`
+    /// in a dependency. Pass `None` to leave one of them alone. The `Screen` keeps rendering to the
+    /// terminal. Like for `Screen::spawn_in_window`, stderr lines use the style set by
+    /// `Screen::set_stderr_style`, and the lines are displayed by `Screen::poll` or a `Bridge`.
+    /// Returns an `OutputCapture` that restores the original descriptors when dropped if
+    /// successful, `Err(std::io::ErrorKind)` if not.
+    pub fn capture_output(
+        &mut self,
+        stdout: Option<usize>,
+        stderr: Option<usize>,
+    ) -> Result<OutputCapture, std::io::ErrorKind> {
+        for id in stdout.iter().chain(stderr.iter()) {
+            self.validate_id(*id)?;
+        }
+        // Keep the terminal before it is replaced
+        if self.tty.is_none() {
+            let tty = capture::duplicate(libc::STDOUT_FILENO).map_err(|error| error.kind())?;
+            self.tty = Some(tty);
+        }
+        capture::capture(stdout, stderr, self.sender.clone()).map_err(|error| error.kind())
+    }
+    /// Also write the lines of window with `id` to the file described by `sink` when they are
+    /// flushed, or stop writing them with `None`. Errors while writing are kept, see
+    /// `Screen::file_sink_errors`. Returns `()` if successful, `Err(std::io::ErrorKind)` if the
+    /// window doesn't exist or the file can't be opened.
+    pub fn set_file_sink(&mut self, id: usize, sink: Option<FileSink>) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let sink = sink.map(sink::Sink::open).transpose().map_err(|error| error.kind())?;
+        self.windows[id].as_mut().unwrap().sink = sink;
+        Ok(())
+    }
+    /// Write the lines of all the windows to the file described by `sink`, after the name of their
+    /// window like `[Window 1]`, or stop writing them with `None`. Returns `()` if successful, `Err(std::io::ErrorKind)` if the file can't be opened.
+    pub fn set_screen_file_sink(&mut self, sink: Option<FileSink>) -> Result<(), std::io::ErrorKind> {
+        let sink = sink.map(sink::Sink::open).transpose().map_err(|error| error.kind())?;
+        self.sink = sink.map(|sink| std::sync::Arc::new(std::sync::Mutex::new(sink)));
+        for window in self.windows.iter_mut().flatten() {
+            window.screen_sink = self.sink.clone();
+        }
+        Ok(())
+    }
+    /// Errors while writing to the files set with `Screen::set_file_sink` and
+    /// `Screen::set_screen_file_sink` since the last call. Lines that fail to be written are lost,
+    /// but they are still displayed.
+    pub fn file_sink_errors(&mut self) -> Vec<std::io::Error> {
+        let mut errors = vec![];
+        for window in self.windows.iter_mut().flatten() {
+            if let Some(sink) = &mut window.sink {
+                errors.extend(sink.take_errors());
+            }
+        }
+        if let Some(sink) = &self.sink {
+            errors.extend(sink.lock().unwrap().take_errors());
+        }
+        errors
+    }
+    /// Text of every line kept by window with `id` in `format`, or of the rows of its canvas or
+    /// terminal. Charts are exported at the size they were last displayed, or 80 by 8 characters
+    /// before that. Returns the text if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Format, Screen};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.println(0, "\x1b[32mOK\x1b[0m").unwrap();
+    /// std::fs::write("window.html", screen.export(0, Format::Html).unwrap()).unwrap();
+    /// ```
+    pub fn export(&self, id: usize, format: Format) -> Result<String, std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let window = self.windows[id].as_ref().unwrap();
+        Ok(export::render(window.get_name(), &window.contents(), format))
+    }
+    /// Export every window as plain text to a file in directory `dir`, named after the window, e.g.
+    /// `Window 1.txt`. Same as `Screen::export_all_as` with `Format::Text`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn export_all(&self, dir: impl AsRef<std::path::Path>) -> Result<(), std::io::ErrorKind> {
+        self.export_all_as(dir, Format::Text)
+    }
+    /// Export every window with `Screen::export` to a file in directory `dir`, named after the
+    /// window, e.g. `Window 1.html`. Windows with the same name get their id added, and a number after it if that name is taken too. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn export_all_as(&self, dir: impl AsRef<std::path::Path>, format: Format) -> Result<(), std::io::ErrorKind> {
+        let mut names = std::collections::HashSet::new();
+        for window in self.windows.iter().flatten() {
+            let mut name = export::file_name(window.get_name(), format);
+            // Add the id, then a number after it, until the name isn't taken
+            let mut suffix = 1;
+            while !names.insert(name.clone()) {
+                name = match suffix {
+                    1 => format!("{}-{}", window.get_name(), window.get_id()),
+                    suffix => format!("{}-{}-{}", window.get_name(), window.get_id(), suffix),
+                };
+                name = export::file_name(&name, format);
+                suffix += 1;
+            }
+            let text = export::render(window.get_name(), &window.contents(), format);
+            std::fs::write(dir.as_ref().join(name), text).map_err(|error| error.kind())?;
+        }
+        Ok(())
+    }
+    /// Set the `Style` of the stderr lines of commands started with `spawn_in_window`. By default,
+    /// they look like stdout lines.
+    pub fn set_stderr_style(&mut self, style: Style) {
+        self.stderr_style = style;
+    }
+    /// Display when every line of window with `id` arrived before it, as set by `Timestamps`. Lines
+    /// record their arrival time even while it is not displayed. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Screen, Timestamps};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_timestamps(0, Timestamps::Clock("%H:%M:%S".to_string())).unwrap();
+    /// screen.println(0, "Compiling...").unwrap();
+    /// ```
+    pub fn set_timestamps(&mut self, id: usize, timestamps: Timestamps) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().timestamps = timestamps;
+        self.load();
+        Ok(())
+    }
+    /// Replace the `Clock` used for the arrival time of lines, and the clock and elapsed time
+    /// segments of the header and footer. The elapsed time and relative timestamps start counting
+    /// from the current time of `clock`.
+    pub fn set_clock(&mut self, clock: std::sync::Arc<dyn Clock>) {
+        self.start = clock.now();
+        for window in self.windows.iter_mut().flatten() {
+            window.buffer.set_clock(clock.clone());
+            window.start = self.start;
+        }
+        self.clock = clock;
+    }
+    /// Search `pattern` in window with `id`, and scroll back to the last match at or above the
+    /// bottom of the window. Matches are highlighted, and the title shows how many lines match.
+    /// Returns the amount of matching lines if successful, `Err(std::io::ErrorKind::InvalidInput)`
+    /// if the regular expression is invalid, or `Err(std::io::ErrorKind)` if the window doesn't exist.
+    ///
+    /// ```ignore
+    /// use multi_window_output::{Screen, Pattern};
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.println(0, "error: file not found").unwrap();
+    /// screen.println(0, "done").unwrap();
+    /// screen.search(0, Pattern::Regex("^error".to_string())).unwrap();
+    /// screen.search_next(0).unwrap();
+    /// screen.clear_search(0).unwrap();
+    /// ```
+    pub fn search(&mut self, id: usize, pattern: Pattern) -> Result<usize, std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let search = Search::new(&pattern, self.search_style).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+        let matches = self.windows[id].as_mut().unwrap().search(search);
+        self.load();
+        Ok(matches)
+    }
+    /// Scroll window with `id` down to the next match of its search. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn search_next(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().next_match();
+        self.load();
+        Ok(())
+    }
+    /// Scroll window with `id` up to the previous match of its search. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn search_prev(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().previous_match();
+        self.load();
+        Ok(())
+    }
+    /// Stop highlighting the matches of the search in window with `id`. The scroll position is kept.
+    /// Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn clear_search(&mut self, id: usize) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().clear_search();
+        self.load();
+        Ok(())
+    }
+    /// Display only the lines of window with `id` that match the regular expression `include`, and
+    /// don't match `exclude`. Lines that are not displayed are kept, so changing or removing the
+    /// filter displays them again. Pass `None` for both to remove the filter. While a filter is
+    /// active, scrolling and search only see the displayed lines, and the title shows `[filtered]`.
+    /// Returns `()` if successful, `Err(std::io::ErrorKind::InvalidInput)` if a regular expression
+    /// is invalid, or `Err(std::io::ErrorKind)` if the window doesn't exist.
+    ///
+    /// ```ignore
+    /// use multi_window_output::Screen;
+    ///
+    /// let mut screen = Screen::new();
+    /// screen.set_filter(0, Some("error|warning"), Some("deprecated")).unwrap();
+    /// // Display all the lines for a while
+    /// screen.set_filter_enabled(0, false).unwrap();
+    /// screen.set_filter_enabled(0, true).unwrap();
+    /// // Remove the filter
+    /// screen.set_filter(0, None, None).unwrap();
+    /// ```
+    pub fn set_filter(&mut self, id: usize, include: Option<&str>, exclude: Option<&str>) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        let filter = match (include, exclude) {
+            (None, None) => None,
+            _ => Some(Filter::new(include, exclude).map_err(|_| std::io::ErrorKind::InvalidInput)?),
+        };
+        self.windows[id].as_mut().unwrap().set_filter(filter);
+        self.load();
+        Ok(())
+    }
+    /// Turn the filter of window with `id` off or back on, without removing it. It does nothing if
+    /// the window has no filter. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_filter_enabled(&mut self, id: usize, enabled: bool) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_filter_enabled(enabled);
+        self.load();
+        Ok(())
+    }
+    /// Set the `Style` of the matches of searches started from now on. By default, they are black on yellow.
+    pub fn set_search_style(&mut self, style: Style) {
+        self.search_style = style;
+    }
+    /// Apply the content sent by background threads, such as the output of programs started with
+    /// `Screen::spawn_terminal` or `Screen::spawn_in_window`, and refresh the `Screen` if needed. It is not needed once the `Screen`
+    /// is passed to a `Bridge`.
+    pub fn poll(&mut self) {
+        while let Ok(msg) = self.receiver.try_recv() {
+            self.apply(msg);
+        }
+    }
+    // Apply a message sent by a `Bridge` or a background thread
+    fn apply(&mut self, msg: (Cmds, usize, String)) {
+        match msg.0 {
+            Cmds::Print => self.print(msg.1, &msg.2).unwrap(),
+            Cmds::Flush => self.flush(msg.1).unwrap(),
+            Cmds::Println => self.println(msg.1, &msg.2).unwrap(),
+            Cmds::Show => self.set_visible(msg.1, true).unwrap(),
+            Cmds::Hide => self.set_visible(msg.1, false).unwrap(),
+            Cmds::Refresh => self.refresh(),
+            Cmds::Stderr => {
+                let style = self.stderr_style;
+                let window = self.windows[msg.1].as_mut().unwrap();
+                window.print_styled(&msg.2, style);
+                window.flush();
+                self.load();
+            }
+            Cmds::Status => {
+                self.windows[msg.1].as_mut().unwrap().status = Some(msg.2);
+                self.load();
+            }
+            Cmds::Styled(line) => self.println_styled(msg.1, line).unwrap(),
+            Cmds::ProgressBar(bar, progress) => self.set_progress_bar(msg.1, bar, progress).unwrap(),
+            Cmds::Progress(bar, value) => self.set_progress(msg.1, bar, value).unwrap(),
+            Cmds::Table(columns) => self.set_table(msg.1, columns).unwrap(),
+            Cmds::Row(values) => {
+                let values: Vec<&str> = values.iter().map(String::as_str).collect();
+                self.set_row(msg.1, &msg.2, &values).unwrap();
+            }
+            Cmds::RemoveRow => self.remove_row(msg.1, &msg.2).unwrap(),
+            Cmds::Sort(column, descending) => self.sort_table(msg.1, column, descending).unwrap(),
+            Cmds::Chart(chart) => self.set_chart(msg.1, chart).unwrap(),
+            Cmds::Sample(sample) => self.push_sample(msg.1, sample).unwrap(),
+            Cmds::StatusValue(value, style) => self.set_status_styled(msg.1, &msg.2, &value, style).unwrap(),
+            Cmds::RemoveStatus => self.remove_status(msg.1, &msg.2).unwrap(),
+            Cmds::SetLine(row) => self.set_line(msg.1, row, &msg.2).unwrap(),
+            Cmds::Clear => self.clear_window(msg.1).unwrap(),
+            Cmds::Filter(include, exclude) => self.set_filter(msg.1, include.as_deref(), exclude.as_deref()).unwrap(),
+            Cmds::FilterEnabled(enabled) => self.set_filter_enabled(msg.1, enabled).unwrap(),
+            Cmds::Replace => {
+                let lines: Vec<&str> = msg.2.split('\n').collect();
+                self.replace_contents(msg.1, &lines).unwrap();
+            }
+            // Handled by `Bridge`
+            Cmds::Break | Cmds::Clone | Cmds::Drop => {}
+        }
+    }
+    /// Set how many lines window with `id` keeps. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_window_capacity(&mut self, id: usize, capacity: Capacity) -> Result<(), std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        self.windows[id].as_mut().unwrap().set_capacity(capacity);
+        Ok(())
+    }
+    /// Set the `Capacity` of windows created from now on. By default, windows keep 64 lines.
+    pub fn set_default_capacity(&mut self, capacity: Capacity) {
+        self.capacity = capacity;
+    }
+    /// Bytes of text stored in window with `id`. Returns `Err(std::io::ErrorKind)` if window doesn't exist.
+    pub fn window_memory_usage(&self, id: usize) -> Result<usize, std::io::ErrorKind> {
+        // Validate if child exists
+        self.validate_id(id)?;
+        Ok(self.windows[id].as_ref().unwrap().buffer.bytes())
+    }
+    /// Bytes of text stored in all the windows of the `Screen`.
+    pub fn memory_usage(&self) -> usize {
+        self.windows
+            .iter()
+            .flatten()
+            .map(|window| window.buffer.bytes())
+            .sum()
+    }
+    /// Set the header displayed at the top of the `Screen`. By default, it displays `Screen: {name}`.
+    pub fn set_header(&mut self, header: Bar) {
+        self.header = header;
+    }
+    /// Set the footer displayed at the bottom of the `Screen`, or remove it with `None`. By default, there is no footer.
+    pub fn set_footer(&mut self, footer: Option<Bar>) {
+        self.footer = footer;
+    }
+    /// Render the `Screen` again without changing any window, e.g. to update a clock in the header.
+    pub fn refresh(&mut self) {
+        self.load();
+    }
     pub fn set_screen_color(&mut self, color: Color){
         self.color = color;
     }
@@ -403,10 +1477,46 @@ impl Default for Screen {
     }
 }
 
-enum Cmds {
+pub(crate) enum Cmds {
     Print,
     Flush,
     Println,
+    Show,
+    Hide,
+    Refresh,
+    // Line of a command's stderr
+    Stderr,
+    // Text displayed in the window's title
+    Status,
+    // Line of a canvas, in the given row
+    SetLine(usize),
+    Clear,
+    // Lines of a canvas, separated by new line characters
+    Replace,
+    // Line with styled spans
+    Styled(Line),
+    // Progress bar of the given row
+    ProgressBar(usize, ProgressBar),
+    // Value of the progress bar of the given row
+    Progress(usize, u64),
+    // Columns of a table
+    Table(Vec<Column>),
+    // Values of the table row with the key in the message
+    Row(Vec<String>),
+    // Table row with the key in the message
+    RemoveRow,
+    // Column the table is sorted by, and if in descending order
+    Sort(Option<usize>, bool),
+    // How the samples are drawn
+    Chart(Chart),
+    Sample(f64),
+    // Value and style of the status panel key in the message
+    StatusValue(String, Style),
+    // Status panel key in the message
+    RemoveStatus,
+    // Include and exclude regular expressions
+    Filter(Option<String>, Option<String>),
+    FilterEnabled(bool),
     Break,
     Clone,
     Drop,
@@ -435,7 +1545,7 @@ pub struct Bridge {
 impl Bridge {
     /// Create a `Bridge` by passing an already created `Screen`. You won't be able to modify the `Screen` once it's passed.
     pub fn new(screen: Screen) -> Self {
-        let (tx, rx) = std::sync::mpsc::channel::<(Cmds, usize, String)>();
+        let tx = screen.sender.clone();
         let mut hash: std::collections::HashSet<usize> = std::collections::HashSet::new();
         let ids: Vec<usize> = screen
             .windows
@@ -454,11 +1564,8 @@ impl Bridge {
             let mut screen = *screen;
             let mut bridge_count = 1;
             // TODO currently user has to drop Bridge for it to fully terminate
-            for msg in rx.iter() {
+            while let Ok(msg) = screen.receiver.recv() {
                 match msg.0 {
-                    Cmds::Print => screen.print(msg.1, &msg.2).unwrap(),
-                    Cmds::Flush => screen.flush(msg.1).unwrap(),
-                    Cmds::Println => screen.println(msg.1, &msg.2).unwrap(),
                     Cmds::Break => break,
                     Cmds::Clone => bridge_count += 1,
                     Cmds::Drop => {
@@ -467,6 +1574,7 @@ impl Bridge {
                             break;
                         }
                     },
+                    _ => screen.apply(msg),
                 };
             }
         });
@@ -488,12 +1596,172 @@ impl Bridge {
             .unwrap();
         Ok(())
     }
+    /// Create a `WindowWriter` that prints in window with `id`, like `Screen::writer`. It can be sent to other threads. Returns the `WindowWriter` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn writer(&self, id: usize) -> Result<WindowWriter<'static>, std::io::ErrorKind> {
+        self.validate_id(id)?;
+        Ok(WindowWriter::new(Target::Bridge(self.clone()), id))
+    }
     /// Flush window with `id`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
     pub fn flush(&self, id: usize) -> Result<(), std::io::ErrorKind> {
         self.validate_id(id)?;
         self.bridge.send((Cmds::Flush, id, "".to_string())).unwrap();
         Ok(())
     }
+    /// Hide or show window with `id`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_visible(&self, id: usize, visible: bool) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        let cmd = if visible { Cmds::Show } else { Cmds::Hide };
+        self.bridge.send((cmd, id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Run `command`, and print its stdout and stderr in window with `id` line by line. Once it
+    /// exits, its exit status and runtime are displayed in the window's title. Returns a
+    /// `ProcessHandle` to wait for or kill the command if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn spawn_in_window(
+        &self,
+        id: usize,
+        command: std::process::Command,
+    ) -> Result<ProcessHandle, std::io::ErrorKind> {
+        self.validate_id(id)?;
+        // The `running` status is only sent once the command started
+        process::spawn(command, id, self.bridge.clone()).map_err(|error| error.kind())
+    }
+    /// Write `text` in `row` of window with `id`, like `Screen::set_line`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_line(&self, id: usize, row: usize, text: &str) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::SetLine(row), id, text.to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Replace all the rows of window with `id`, like `Screen::replace_contents`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn replace_contents(&self, id: usize, lines: &[&str]) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::Replace, id, lines.join("\n")))
+            .unwrap();
+        Ok(())
+    }
+    /// Remove the content of window with `id`, like `Screen::clear_window`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn clear_window(&self, id: usize) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::Clear, id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Filter the lines displayed by window with `id`, like `Screen::set_filter`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_filter(&self, id: usize, include: Option<&str>, exclude: Option<&str>) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        // Check the regular expressions here, the `Screen` can't return errors
+        Filter::new(include, exclude).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+        let cmd = Cmds::Filter(include.map(str::to_string), exclude.map(str::to_string));
+        self.bridge.send((cmd, id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Turn the filter of window with `id` off or back on, like `Screen::set_filter_enabled`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_filter_enabled(&self, id: usize, enabled: bool) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::FilterEnabled(enabled), id, "".to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Print a `Line` made of differently styled `Span`s in window with `id`, like `Screen::println_styled`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn println_styled(&self, id: usize, line: Line) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::Styled(line), id, "".to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Display `progress` in the row of `bar` of window with `id`, like `Screen::set_progress_bar`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_progress_bar(&self, id: usize, bar: usize, progress: ProgressBar) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::ProgressBar(bar, progress), id, "".to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Set the current value of progress bar `bar` of window with `id`, like `Screen::set_progress`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_progress(&self, id: usize, bar: usize, value: u64) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::Progress(bar, value), id, "".to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Display a table with `columns` in window with `id`, like `Screen::set_table`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_table(&self, id: usize, columns: Vec<Column>) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::Table(columns), id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Set the `values` of the row with `key` of the table in window with `id`, like `Screen::set_row`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_row(&self, id: usize, key: &str, values: &[&str]) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        let values = values.iter().map(|value| value.to_string()).collect();
+        self.bridge.send((Cmds::Row(values), id, key.to_string())).unwrap();
+        Ok(())
+    }
+    /// Remove the row with `key` of the table in window with `id`, like `Screen::remove_row`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn remove_row(&self, id: usize, key: &str) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::RemoveRow, id, key.to_string())).unwrap();
+        Ok(())
+    }
+    /// Sort the rows of the table in window with `id`, like `Screen::sort_table`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn sort_table(&self, id: usize, column: Option<usize>, descending: bool) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::Sort(column, descending), id, "".to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Draw the samples of window with `id` with `chart`, like `Screen::set_chart`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_chart(&self, id: usize, chart: Chart) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::Chart(chart), id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Add `sample` to the chart of window with `id`, like `Screen::push_sample`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn push_sample(&self, id: usize, sample: f64) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::Sample(sample), id, "".to_string())).unwrap();
+        Ok(())
+    }
+    /// Set the `value` of `key` in the status panel of window with `id`, like `Screen::set_status`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_status(&self, id: usize, key: &str, value: &str) -> Result<(), std::io::ErrorKind> {
+        self.set_status_styled(id, key, value, Style::default())
+    }
+    /// Set the `value` of `key` in the status panel of window with `id` displayed with `style`, like `Screen::set_status_styled`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn set_status_styled(&self, id: usize, key: &str, value: &str, style: Style) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge
+            .send((Cmds::StatusValue(value.to_string(), style), id, key.to_string()))
+            .unwrap();
+        Ok(())
+    }
+    /// Remove `key` from the status panel of window with `id`, like `Screen::remove_status`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+    pub fn remove_status(&self, id: usize, key: &str) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        self.bridge.send((Cmds::RemoveStatus, id, key.to_string())).unwrap();
+        Ok(())
+    }
+    // Display `status` in the title of window with `id`
+    #[cfg(feature = "tracing")]
+    pub(crate) fn set_title_status(&self, id: usize, status: &str) {
+        let _ = self.try_send(Cmds::Status, id, status);
+    }
+    // Send a command to window with `id` without panicking once the `Screen` is gone, for loggers.
+    // Returns `Err(NotFound)` if the window doesn't exist, `Err(BrokenPipe)` if the `Screen` is gone
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    pub(crate) fn try_send(&self, cmd: Cmds, id: usize, text: &str) -> Result<(), std::io::ErrorKind> {
+        self.validate_id(id)?;
+        (self.bridge.send((cmd, id, text.to_string()))).map_err(|_| std::io::ErrorKind::BrokenPipe)
+    }
+    /// Render the `Screen` again without changing any window, e.g. to update a clock in the header.
+    pub fn refresh(&self) {
+        self.bridge.send((Cmds::Refresh, 0, "".to_string())).unwrap();
+    }
     fn validate_id(&self, id: usize) -> Result<(), std::io::ErrorKind> {
         if self.hash.contains(&id) {
             return Ok(());
@@ -520,6 +1788,7 @@ impl std::clone::Clone for Bridge {
 
 impl std::ops::Drop for Bridge {
     fn drop(&mut self){
-        self.bridge.send((Cmds::Drop, 0, "".to_string())).unwrap();
+        // The `Screen` is already gone if the `Bridge` was killed
+        let _ = self.bridge.send((Cmds::Drop, 0, "".to_string()));
     }
 }
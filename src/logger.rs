@@ -0,0 +1,185 @@
+use crate::colors::{self, Color, Style};
+use crate::routing::path_matches;
+use crate::{Bridge, Cmds};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::io::ErrorKind;
+
+#[cfg(test)]
+mod logger_tests {
+    use super::*;
+    use crate::Screen;
+    fn record<'a>(level: Level, target: &'a str, args: std::fmt::Arguments<'a>) -> Record<'a> {
+        Record::builder()
+            .level(level)
+            .target(target)
+            .module_path(Some("app::net::client"))
+            .args(args)
+            .build()
+    }
+    #[test]
+    fn routing_table() {
+        let mut screen = Screen::new();
+        screen.append_left_child(0).unwrap();
+        screen.append_down_child(0).unwrap();
+        screen.append_left_child(1).unwrap();
+        let logger = Logger::new(Bridge::new(screen), 0)
+            .route(Route::Level(Level::Error), 1)
+            .route(Route::Target("net::*".to_string()), 2)
+            .route(Route::Module("app::db".to_string()), 3);
+        assert_eq!(logger.window(&record(Level::Error, "net::tcp", format_args!(""))), 1);
+        assert_eq!(logger.window(&record(Level::Info, "net::tcp", format_args!(""))), 2);
+        assert_eq!(logger.window(&record(Level::Info, "network", format_args!(""))), 0);
+        // Module paths match whole segments
+        let logger = logger.route(Route::Module("app::net".to_string()), 3);
+        assert_eq!(logger.window(&record(Level::Info, "other", format_args!(""))), 3);
+    }
+    #[test]
+    fn level_colors() {
+        let logger = Logger::new(Bridge::new(Screen::new()), 0);
+        let line = logger.format(&record(Level::Warn, "net", format_args!("slow {}", 3)));
+        let style = colors::style_code(&Style { fg: Color::Yellow, bold: true, ..Style::default() });
+        assert_eq!(line, format!("{}WARN {} net: slow 3", style, colors::style_code(&Style::default())));
+    }
+    #[test]
+    fn log_after_kill() {
+        let logger = Logger::new(Bridge::new(Screen::new()), 0);
+        logger.bridge.kill();
+        // Wait for the `Screen` to be gone
+        while logger.bridge.try_send(Cmds::Refresh, 0, "").is_ok() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        logger.log(&record(Level::Info, "net", format_args!("dropped")));
+        logger.log(&record(Level::Info, "net", format_args!("dropped")));
+    }
+}
+
+/// Part of a log record that decides its window in a `Logger`'s routing table.
+#[derive(Clone, Debug)]
+pub enum Route {
+    /// Records whose target is this one, or is inside it like a module path. Ending it with `*`
+    /// matches any target that starts with the rest, e.g. `net::*` matches `net::tcp`.
+    Target(String),
+    /// Records whose module path is this one or inside it, with the same syntax as `Route::Target`.
+    Module(String),
+    /// Records of this level.
+    Level(Level),
+}
+
+impl Route {
+    fn matches(&self, record: &Record) -> bool {
+        match self {
+            Route::Target(target) => path_matches(target, record.target()),
+            Route::Module(module) => record.module_path().is_some_and(|path| path_matches(module, path)),
+            Route::Level(level) => record.level() == *level,
+        }
+    }
+}
+
+/// A `Logger` prints the records of the `log` crate in the windows of a `Bridge`, so the existing
+/// `info!` or `warn!` calls of a program populate its windows. Records are sent to the window of
+/// the first route of the routing table they match, or to the fallback window. Their level is
+/// colored. Requires the `log` feature.
+///
+/// ```ignore
+/// use multi_window_output::{Bridge, Logger, Route, Screen};
+/// use log::{Level, LevelFilter};
+///
+/// let mut screen = Screen::new();
+/// let network = screen.append_left_child(0).unwrap();
+/// let errors = screen.append_down_child(0).unwrap();
+///
+/// Logger::new(Bridge::new(screen), 0)
+///     .route(Route::Level(Level::Error), errors)
+///     .route(Route::Target("net::*".to_string()), network)
+///     .max_level(LevelFilter::Debug)
+///     .init()
+///     .unwrap();
+///
+/// log::info!(target: "net::tcp", "connected");
+/// log::error!("disk full");
+/// ```
+pub struct Logger {
+    bridge: Bridge,
+    routes: Vec<(Route, usize)>,
+    fallback: usize,
+    max_level: LevelFilter,
+    styles: [Style; 5],
+}
+
+impl Logger {
+    /// Create a `Logger` that prints every record in window `fallback` of `bridge`, until routes are added.
+    pub fn new(bridge: Bridge, fallback: usize) -> Logger {
+        let style = |fg| Style { fg, bold: true, ..Style::default() };
+        Logger {
+            bridge,
+            routes: vec![],
+            fallback,
+            max_level: LevelFilter::Trace,
+            styles: [
+                style(Color::Red),
+                style(Color::Yellow),
+                style(Color::Green),
+                style(Color::Blue),
+                style(Color::LightBlack),
+            ],
+        }
+    }
+    /// Print the records that match `route` in window with `id`. Routes are checked in the order they are added.
+    pub fn route(mut self, route: Route, id: usize) -> Self {
+        self.routes.push((route, id));
+        self
+    }
+    /// Ignore the records less severe than `level`. By default, all records are printed.
+    pub fn max_level(mut self, level: LevelFilter) -> Self {
+        self.max_level = level;
+        self
+    }
+    /// Set the `Style` of the level of the records of `level`.
+    pub fn level_style(mut self, level: Level, style: Style) -> Self {
+        self.styles[level as usize - 1] = style;
+        self
+    }
+    /// Make this `Logger` the one used by the `log` crate. It fails if there already is one.
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        let max_level = self.max_level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+    // Window of the first route that matches `record`
+    fn window(&self, record: &Record) -> usize {
+        self.routes
+            .iter()
+            .find(|(route, _)| route.matches(record))
+            .map_or(self.fallback, |(_, id)| *id)
+    }
+    // Line of `record`, its level is styled with escape sequences
+    fn format(&self, record: &Record) -> String {
+        format!(
+            "{}{:<5}{} {}: {}",
+            colors::style_code(&self.styles[record.level() as usize - 1]),
+            record.level(),
+            colors::style_code(&Style::default()),
+            record.target(),
+            record.args()
+        )
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.max_level
+    }
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = self.format(record);
+        // Records routed to windows that don't exist go to the fallback window, and records logged
+        // once the `Screen` is gone are dropped
+        if self.bridge.try_send(Cmds::Println, self.window(record), &line) == Err(ErrorKind::NotFound) {
+            let _ = self.bridge.try_send(Cmds::Println, self.fallback, &line);
+        }
+    }
+    fn flush(&self) {}
+}
@@ -0,0 +1,200 @@
+use crate::Cmds;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// Time between checks of the process exit
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+// Time the output is waited for after the process exits, it doesn't end while other processes have it
+const OUTPUT_WAIT: Duration = Duration::from_millis(250);
+
+#[cfg(test)]
+mod process_tests {
+    use super::*;
+    #[test]
+    fn stream_output() {
+        let _fds = crate::lock_fds();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 3"]);
+        let handle = spawn(command, 2, tx).unwrap();
+        assert_eq!(handle.wait().code(), Some(3));
+
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        loop {
+            let (cmd, id, text) = rx.recv().unwrap();
+            assert_eq!(id, 2);
+            match cmd {
+                Cmds::Println => stdout.push(text),
+                Cmds::Stderr => stderr.push(text),
+                Cmds::Status if text == "running" => {}
+                Cmds::Status => {
+                    assert!(text.starts_with("exit 3 in "));
+                    break;
+                }
+                _ => panic!("unexpected command"),
+            }
+        }
+        assert_eq!(stdout, vec!["out"]);
+        assert_eq!(stderr, vec!["err"]);
+    }
+    #[test]
+    fn background_grandchild() {
+        let _fds = crate::lock_fds();
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let mut command = Command::new("sh");
+        // The grandchild keeps the output open after the command exits
+        command.args(["-c", "sleep 5 &"]);
+        let start = Instant::now();
+        let handle = spawn(command, 0, tx).unwrap();
+        assert_eq!(handle.wait().code(), Some(0));
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+}
+
+/// A `ProcessHandle` controls a command started with `Screen::spawn_in_window` or `Bridge::spawn_in_window`.
+/// It can be cloned and sent to other threads.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Bridge};
+/// use std::process::Command;
+///
+/// let screen = Screen::new();
+/// let bridge = Bridge::new(screen);
+///
+/// let mut command = Command::new("cargo");
+/// command.arg("build");
+/// let build = bridge.spawn_in_window(0, command).unwrap();
+/// if !build.wait().success() {
+///     bridge.println(0, "Build failed").unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProcessHandle {
+    id: u32,
+    child: Arc<Mutex<Child>>,
+    status: Arc<(Mutex<Option<ExitStatus>>, Condvar)>,
+}
+
+impl ProcessHandle {
+    /// Process id of the command.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    /// Kill the command. It does nothing if the command already exited.
+    pub fn kill(&self) -> std::io::Result<()> {
+        match self.try_wait() {
+            Some(_) => Ok(()),
+            None => self.child.lock().unwrap().kill(),
+        }
+    }
+    /// Wait for the command to exit, and its output to be sent to the window. Output of other
+    /// processes it started that is still open is waited for a moment only. If the exit status
+    /// can't be read, the command is considered to have exited with code 255.
+    pub fn wait(&self) -> ExitStatus {
+        let (status, exited) = &*self.status;
+        let mut status = status.lock().unwrap();
+        loop {
+            match *status {
+                Some(status) => return status,
+                None => status = exited.wait(status).unwrap(),
+            }
+        }
+    }
+    /// Return the exit status of the command if it has exited, without waiting.
+    pub fn try_wait(&self) -> Option<ExitStatus> {
+        *self.status.0.lock().unwrap()
+    }
+}
+
+// Describe how a process ended
+fn summary(status: ExitStatus, runtime: Duration) -> String {
+    match (status.code(), status.signal()) {
+        (Some(code), _) => format!("exit {} in {:.2?}", code, runtime),
+        (None, Some(signal)) => format!("signal {} in {:.2?}", signal, runtime),
+        (None, None) => format!("{} in {:.2?}", status, runtime),
+    }
+}
+
+// Send every line of `output` to the window with `cmd`
+pub fn stream(
+    output: impl Read + Send + 'static,
+    cmd: fn() -> Cmds,
+    id: usize,
+    sender: Sender<(Cmds, usize, String)>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut output = BufReader::new(output);
+        let mut line = vec![];
+        while let Ok(1..) = output.read_until(b'\n', &mut line) {
+            if line.ends_with(b"\n") {
+                line.pop();
+            }
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+            let text = String::from_utf8_lossy(&line).into_owned();
+            if sender.send((cmd(), id, text)).is_err() {
+                break;
+            }
+            line.clear();
+        }
+    })
+}
+
+// Start `command` and send its output to window with `id` through `sender`, after a `running`
+// status. Once it exits, its exit status and runtime are sent too
+pub fn spawn(
+    mut command: Command,
+    id: usize,
+    sender: Sender<(Cmds, usize, String)>,
+) -> std::io::Result<ProcessHandle> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Sent before anything else, so it can't replace the exit status
+    let _ = sender.send((Cmds::Status, id, "running".to_string()));
+    let readers = [
+        stream(child.stdout.take().unwrap(), || Cmds::Println, id, sender.clone()),
+        stream(child.stderr.take().unwrap(), || Cmds::Stderr, id, sender.clone()),
+    ];
+    let handle = ProcessHandle {
+        id: child.id(),
+        child: Arc::new(Mutex::new(child)),
+        status: Arc::new((Mutex::new(None), Condvar::new())),
+    };
+
+    let waiter = handle.clone();
+    std::thread::spawn(move || {
+        // Check without blocking so the handle can kill it meanwhile
+        let status = loop {
+            match waiter.child.lock().unwrap().try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) => {}
+                Err(error) => break Err(error),
+            }
+            std::thread::sleep(WAIT_INTERVAL);
+        };
+        // Let the readers send the rest of the output first, unless other processes keep it open
+        let deadline = Instant::now() + OUTPUT_WAIT;
+        while readers.iter().any(|reader| !reader.is_finished()) && Instant::now() < deadline {
+            std::thread::sleep(WAIT_INTERVAL);
+        }
+        let (status, text) = match status {
+            Ok(status) => (status, summary(status, start.elapsed())),
+            Err(error) => (ExitStatus::from_raw(255 << 8), format!("unknown exit: {}", error)),
+        };
+        let _ = sender.send((Cmds::Status, id, text));
+        let (exit, exited) = &*waiter.status;
+        *exit.lock().unwrap() = Some(status);
+        exited.notify_all();
+    });
+    Ok(handle)
+}
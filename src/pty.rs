@@ -0,0 +1,211 @@
+use crate::utf8::Decoder;
+use crate::window::{Cell, Terminal};
+use crate::Cmds;
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+// Default size of the pseudo-terminal before the window is displayed
+const WIDTH: usize = 80;
+const HEIGHT: usize = 24;
+
+#[cfg(test)]
+mod pty_tests {
+    use super::*;
+    #[test]
+    fn run_in_terminal() {
+        let _fds = crate::lock_fds();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut command = Command::new("printf");
+        command.arg("a\\033[31mb");
+        let (session, mut handle) = spawn(command, 0, 0, tx).unwrap();
+        assert!(handle.wait().unwrap().success());
+
+        // Wait for the output to be read
+        assert!(matches!(rx.recv().unwrap().0, Cmds::Refresh));
+        while session.terminal.lock().unwrap().rows()[0][1].0 != 'b' {
+            std::thread::yield_now();
+        }
+        let rows = session.rows(4, 2);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0].0, 'a');
+        assert_eq!(rows[0][1].1.fg, crate::Color::Red);
+    }
+}
+
+// Window side of a program running in a pseudo-terminal
+#[derive(Debug)]
+pub struct Session {
+    terminal: Arc<Mutex<Terminal>>,
+    master: File,
+    // Output was read since the last time the window was displayed
+    dirty: Arc<AtomicBool>,
+}
+
+impl Session {
+    // Rows of the terminal, resizing it and the pseudo-terminal to the window size
+    pub fn rows(&self, width: usize, height: usize) -> Vec<Vec<Cell>> {
+        let mut terminal = self.terminal.lock().unwrap();
+        if terminal.size() != (width.max(1), height.max(1)) {
+            terminal.resize(width, height);
+            let _ = set_size(&self.master, width, height);
+        }
+        self.dirty.store(false, Ordering::SeqCst);
+        terminal.rows()
+    }
+    // Blank the screen of the terminal. The program draws over it from then on
+    pub fn clear(&self) {
+        self.terminal.lock().unwrap().clear();
+    }
+    // Rows of the terminal at its current size, without the cursor
+    pub fn cells(&self) -> Vec<Vec<Cell>> {
+        self.terminal.lock().unwrap().cells()
+    }
+}
+
+/// A `TerminalHandle` controls a program started with `Screen::spawn_terminal`. Bytes written to it are
+/// sent to the program as keyboard input.
+///
+/// ```ignore
+/// use multi_window_output::Screen;
+/// use std::io::Write;
+///
+/// let mut screen = Screen::new();
+/// let mut top = screen.spawn_terminal(0, std::process::Command::new("top")).unwrap();
+/// for _ in 0..100 {
+///     // Display the new output of `top`
+///     screen.poll();
+///     std::thread::sleep(std::time::Duration::from_millis(100));
+/// }
+/// // Quit `top`
+/// top.write_all(b"q").unwrap();
+/// top.wait().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TerminalHandle {
+    child: Child,
+    master: File,
+}
+
+impl TerminalHandle {
+    /// Process id of the program.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+    /// Kill the program.
+    pub fn kill(&mut self) -> std::io::Result<()> {
+        self.child.kill()
+    }
+    /// Wait for the program to exit.
+    pub fn wait(&mut self) -> std::io::Result<ExitStatus> {
+        self.child.wait()
+    }
+    /// Return the exit status of the program if it has exited, without waiting.
+    pub fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+}
+
+impl std::io::Write for TerminalHandle {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.master.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.master.flush()
+    }
+}
+
+// Convert C return value into a result
+fn check(value: libc::c_int) -> std::io::Result<libc::c_int> {
+    match value {
+        -1 => Err(std::io::Error::last_os_error()),
+        value => Ok(value),
+    }
+}
+
+fn window_size(width: usize, height: usize) -> libc::winsize {
+    libc::winsize {
+        ws_row: height.max(1) as u16,
+        ws_col: width.max(1) as u16,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
+
+// Tell the program the terminal size changed
+fn set_size(master: &File, width: usize, height: usize) -> std::io::Result<()> {
+    let size = window_size(width, height);
+    // SAFETY: `size` is a valid `winsize` and the descriptor is owned by `master`
+    check(unsafe { libc::ioctl(master.as_raw_fd(), libc::TIOCSWINSZ, &size) })?;
+    Ok(())
+}
+
+// Start `command` in a new pseudo-terminal. Its output is read in a new thread, which asks the
+// `Screen` to refresh through `sender`
+pub fn spawn(
+    mut command: Command,
+    width: usize,
+    height: usize,
+    sender: Sender<(Cmds, usize, String)>,
+) -> std::io::Result<(Session, TerminalHandle)> {
+    let (width, height) = match (width, height) {
+        (0, _) | (_, 0) => (WIDTH, HEIGHT),
+        size => size,
+    };
+    let (mut master, mut slave) = (0, 0);
+    let size = window_size(width, height);
+    // SAFETY: the pointers are valid for the duration of the call
+    check(unsafe {
+        libc::openpty(&mut master, &mut slave, std::ptr::null_mut(), std::ptr::null(), &size)
+    })?;
+    // SAFETY: `openpty` returned two new descriptors that nothing else owns
+    let (master, slave) = unsafe { (File::from_raw_fd(master), File::from_raw_fd(slave)) };
+    // The program must not inherit the master side
+    // SAFETY: the descriptor is owned by `master`
+    check(unsafe { libc::fcntl(master.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) })?;
+
+    command
+        .stdin(Stdio::from(slave.try_clone()?))
+        .stdout(Stdio::from(slave.try_clone()?))
+        .stderr(Stdio::from(slave))
+        .env("TERM", "xterm-256color");
+    // SAFETY: only async-signal-safe functions are called between fork and exec
+    unsafe {
+        command.pre_exec(|| {
+            // Make the pseudo-terminal the controlling terminal of a new session
+            check(libc::setsid())?;
+            check(libc::ioctl(0, libc::TIOCSCTTY, 0))?;
+            Ok(())
+        });
+    }
+    let child = command.spawn()?;
+
+    let terminal = Arc::new(Mutex::new(Terminal::new(width, height)));
+    let dirty = Arc::new(AtomicBool::new(false));
+    let mut reader = master.try_clone()?;
+    let session = Session {
+        terminal: terminal.clone(),
+        master: master.try_clone()?,
+        dirty: dirty.clone(),
+    };
+    std::thread::spawn(move || {
+        let mut decoder = Decoder::new();
+        let mut bytes = [0; 4096];
+        // Reading fails once the program exits
+        while let Ok(read @ 1..) = reader.read(&mut bytes) {
+            terminal.lock().unwrap().feed(&decoder.decode(&bytes[..read]));
+            // Only ask for a refresh if the last one was already displayed
+            if !dirty.swap(true, Ordering::SeqCst)
+                && sender.send((Cmds::Refresh, 0, "".to_string())).is_err()
+            {
+                break;
+            }
+        }
+    });
+    Ok((session, TerminalHandle { child, master }))
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+#[allow(clippy::items_after_test_module)]
+mod routing_tests {
+    use super::*;
+    #[test]
+    fn path_patterns() {
+        assert!(path_matches("net", "net"));
+        assert!(path_matches("net", "net::tcp"));
+        assert!(!path_matches("net", "network"));
+        assert!(path_matches("net*", "network"));
+        assert!(path_matches("net::*", "net::tcp"));
+        assert!(!path_matches("net::*", "net"));
+    }
+}
+
+// Whether a target or module path is `pattern` or inside it. A pattern ending with `*` matches any
+// path that starts with the rest
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => path.starts_with(prefix),
+        None => path.strip_prefix(pattern).is_some_and(|rest| rest.is_empty() || rest.starts_with("::")),
+    }
+}
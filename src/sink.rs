@@ -0,0 +1,200 @@
+use crate::time;
+use crate::window::Line;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod sink_tests {
+    use super::*;
+    use crate::colors::{Color, Style};
+    // Empty directory for the files of a test
+    fn directory(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("multi_window_output_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+    fn line(text: &str, style: Style) -> Line {
+        text.chars().map(|c| (c, style)).collect()
+    }
+    #[test]
+    fn write_lines() {
+        let path = directory("write_lines").join("out.log");
+        let mut sink = Sink::open(FileSink::new(&path).strip_ansi(true)).unwrap();
+        let red = Style { fg: Color::Red, ..Style::default() };
+        sink.write(None, &line("error", red));
+        sink.write(Some("Window 1"), &line("ok", Style::default()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "error\n[Window 1] ok\n");
+        assert!(sink.take_errors().is_empty());
+    }
+    #[test]
+    fn rotation() {
+        let path = directory("rotation").join("out.log");
+        let mut sink = Sink::open(FileSink::new(&path).rotate(8, 2)).unwrap();
+        for text in ["first", "second", "third", "fourth"] {
+            sink.write(None, &line(text, Style::default()));
+        }
+        let read = |suffix: &str| std::fs::read_to_string(format!("{}{}", path.display(), suffix)).unwrap();
+        assert_eq!(read(""), "fourth\n");
+        assert_eq!(read(".1"), "third\n");
+        assert_eq!(read(".2"), "second\n");
+        assert!(!Path::new(&format!("{}.3", path.display())).exists());
+    }
+    #[test]
+    fn report_errors() {
+        let path = directory("report_errors").join("out.log");
+        let mut sink = Sink::open(FileSink::new(&path)).unwrap();
+        // Writing fails once the file can't be opened again
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        sink.file = None;
+        sink.write(None, &line("lost", Style::default()));
+        assert_eq!(sink.take_errors().len(), 1);
+    }
+}
+
+// Errors kept until they are taken, later ones are dropped
+const MAX_ERRORS: usize = 64;
+
+/// A `FileSink` describes a file where the lines of a window are also written when they are
+/// flushed, set with `Screen::set_file_sink` or `Screen::set_screen_file_sink`. Lines keep their
+/// colors as ANSI escape sequences unless they are stripped.
+///
+/// ```ignore
+/// use multi_window_output::{FileSink, Screen};
+///
+/// let mut screen = Screen::new();
+/// let sink = FileSink::new("build.log")
+///     .timestamps("%H:%M:%S")
+///     .strip_ansi(true)
+///     // Keep build.log and up to 3 older files: build.log.1, build.log.2 and build.log.3
+///     .rotate(1 << 20, 3);
+/// screen.set_file_sink(0, Some(sink)).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileSink {
+    path: PathBuf,
+    timestamps: Option<String>,
+    strip_ansi: bool,
+    // Maximum size of the file, and amount of older files kept
+    rotate: Option<(u64, usize)>,
+}
+
+impl FileSink {
+    /// Append lines to the file at `path`, creating it if needed.
+    pub fn new(path: impl AsRef<Path>) -> FileSink {
+        FileSink {
+            path: path.as_ref().to_path_buf(),
+            timestamps: None,
+            strip_ansi: false,
+            rotate: None,
+        }
+    }
+    /// Write when every line arrived before it, in local time with the `strftime` syntax of the C library, e.g. `%H:%M:%S`.
+    pub fn timestamps(mut self, format: &str) -> Self {
+        self.timestamps = Some(format.to_string());
+        self
+    }
+    /// Write the text of the lines without their styles.
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.strip_ansi = strip;
+        self
+    }
+    /// Once the file would grow over `max_bytes`, rename it adding `.1`, and start a new one.
+    /// Older files are renamed to the next number, and at most `keep` of them are kept.
+    pub fn rotate(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.rotate = Some((max_bytes, keep));
+        self
+    }
+}
+
+// Open `FileSink`
+#[derive(Debug)]
+pub struct Sink {
+    config: FileSink,
+    file: Option<File>,
+    size: u64,
+    errors: Vec<std::io::Error>,
+}
+
+impl Sink {
+    pub fn open(config: FileSink) -> std::io::Result<Sink> {
+        let mut sink = Sink {
+            config,
+            file: None,
+            size: 0,
+            errors: vec![],
+        };
+        sink.reopen()?;
+        Ok(sink)
+    }
+    fn reopen(&mut self) -> std::io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+    // Keep `error` to be reported
+    fn report(&mut self, error: std::io::Error) {
+        if self.errors.len() < MAX_ERRORS {
+            self.errors.push(error);
+        }
+    }
+    // Rename the file and the older ones to the next number, dropping the last one
+    fn rotate(&mut self, keep: usize) -> std::io::Result<()> {
+        self.file = None;
+        let path = |n: usize| -> PathBuf {
+            let mut path = self.config.path.clone().into_os_string();
+            if n > 0 {
+                path.push(format!(".{}", n));
+            }
+            path.into()
+        };
+        if keep == 0 {
+            std::fs::remove_file(path(0))?;
+        }
+        for n in (0..keep).rev() {
+            if path(n).exists() {
+                std::fs::rename(path(n), path(n + 1))?;
+            }
+        }
+        self.reopen()
+    }
+    // Write `line`, after `prefix`. Errors are kept instead of returned
+    pub fn write(&mut self, prefix: Option<&str>, line: &Line) {
+        let mut text = String::new();
+        if let (Some(format), Some(time)) = (&self.config.timestamps, line.time) {
+            text.push_str(&time::format_time(time, format));
+            text.push(' ');
+        }
+        if let Some(prefix) = prefix {
+            text.push_str(&format!("[{}] ", prefix));
+        }
+        match self.config.strip_ansi {
+            true => text.push_str(&line.text()),
+            false => text.push_str(&line.ansi()),
+        }
+        text.push('\n');
+
+        if let Some((max_bytes, keep)) = self.config.rotate {
+            if self.size > 0 && self.size + text.len() as u64 > max_bytes {
+                if let Err(error) = self.rotate(keep) {
+                    self.report(error);
+                }
+            }
+        }
+        if self.file.is_none() {
+            if let Err(error) = self.reopen() {
+                return self.report(error);
+            }
+        }
+        match self.file.as_mut().unwrap().write_all(text.as_bytes()) {
+            Ok(()) => self.size += text.len() as u64,
+            Err(error) => self.report(error),
+        }
+    }
+    // Errors since the last call
+    pub fn take_errors(&mut self) -> Vec<std::io::Error> {
+        std::mem::take(&mut self.errors)
+    }
+}
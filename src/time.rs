@@ -0,0 +1,114 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(test)]
+mod time_tests {
+    use super::*;
+    #[test]
+    fn duration_format() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_duration(Duration::from_secs(100 * 3600)), "100:00:00");
+    }
+    #[test]
+    fn time_format() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(format_time(time, "%H:%M:%S"), format_clock(time));
+        assert_eq!(format_time(time, "100%%"), "100%");
+        assert_eq!(format_time(time, "a\0b"), "");
+    }
+}
+
+/// Source of the current time, used for the timestamps of lines and the header and footer segments.
+/// Replace it with `Screen::set_clock`, e.g. to get the same output on every run of a test.
+///
+/// ```ignore
+/// use multi_window_output::{Clock, Screen};
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// // Every line seems to arrive at the same time
+/// struct Frozen;
+///
+/// impl Clock for Frozen {
+///     fn now(&self) -> SystemTime {
+///         UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+///     }
+/// }
+///
+/// let mut screen = Screen::new();
+/// screen.set_clock(std::sync::Arc::new(Frozen));
+/// ```
+pub trait Clock: Send + Sync {
+    /// Current time.
+    fn now(&self) -> SystemTime;
+}
+
+impl std::fmt::Debug for dyn Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Clock")
+    }
+}
+
+/// `Clock` of the operating system. It is the one used by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+// Broken down local time
+pub struct LocalTime {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+// Convert system time to the C library's broken down local time
+fn local_tm(time: SystemTime) -> libc::tm {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as libc::time_t;
+    // SAFETY: `tm` is plain old data and `localtime_r` only writes into it
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&secs, &mut tm);
+        tm
+    }
+}
+
+// Convert system time to local time using the C library
+pub fn local_time(time: SystemTime) -> LocalTime {
+    let tm = local_tm(time);
+    LocalTime {
+        hour: tm.tm_hour as u32,
+        minute: tm.tm_min as u32,
+        second: tm.tm_sec as u32,
+    }
+}
+
+// Format local time as `HH:MM:SS`
+pub fn format_clock(time: SystemTime) -> String {
+    let time = local_time(time);
+    format!("{:02}:{:02}:{:02}", time.hour, time.minute, time.second)
+}
+
+// Format local time with the `strftime` syntax of the C library, e.g. `%H:%M:%S`
+pub fn format_time(time: SystemTime, format: &str) -> String {
+    let tm = local_tm(time);
+    let Ok(format) = std::ffi::CString::new(format) else {
+        return String::new();
+    };
+    let mut bytes = [0u8; 256];
+    // SAFETY: `bytes` is writable for its whole length and `format` is a valid C string
+    let len = unsafe {
+        libc::strftime(bytes.as_mut_ptr() as *mut libc::c_char, bytes.len(), format.as_ptr(), &tm)
+    };
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+// Format duration as `HH:MM:SS`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod utf8_tests {
+    use super::*;
+    #[test]
+    fn split_sequences() {
+        let mut decoder = Decoder::new();
+        let bytes = "añ█".as_bytes();
+        assert_eq!(decoder.decode(&bytes[..2]), "a");
+        assert_eq!(decoder.decode(&bytes[2..4]), "ñ");
+        assert_eq!(decoder.decode(&bytes[4..]), "█");
+        assert_eq!(decoder.decode(&[0xff, b'b']), "\u{fffd}b");
+    }
+    #[test]
+    fn incomplete_end() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.decode(&"añ".as_bytes()[..2]), "a");
+        assert_eq!(decoder.finish(), "\u{fffd}");
+        assert_eq!(decoder.finish(), "");
+    }
+}
+
+// Decode UTF-8 text that arrives in chunks, keeping sequences split between chunks until they are complete
+#[derive(Debug, Default)]
+pub struct Decoder {
+    pending: Vec<u8>,
+}
+
+impl Decoder {
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+    // Decode `bytes` after the pending ones. Invalid sequences are replaced with `U+FFFD`
+    pub fn decode(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+        let mut text = String::new();
+        let mut rest: &[u8] = &self.pending;
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, after) = rest.split_at(error.valid_up_to());
+                    text.push_str(std::str::from_utf8(valid).unwrap());
+                    match error.error_len() {
+                        // Invalid sequence
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        // Incomplete sequence at the end, wait for the rest
+                        None => {
+                            rest = after;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        self.pending = rest.to_vec();
+        text
+    }
+    // Take the incomplete sequence left at the end, if any, replaced with `U+FFFD`
+    pub fn finish(&mut self) -> String {
+        String::from_utf8_lossy(&std::mem::take(&mut self.pending)).into_owned()
+    }
+}
@@ -1,20 +1,198 @@
+mod ansi;
 mod buffer;
+mod chart;
+mod filter;
+mod line;
+mod panel;
+mod progress;
+mod search;
+mod table;
+mod terminal;
+mod timestamps;
+mod wrap;
 use buffer::Buffer;
+pub(crate) use buffer::clean;
+use crate::pty::Session;
+use crate::colors::Style;
+pub use line::Cell;
+pub use terminal::Terminal;
+pub use line::{Line, Span};
+pub use buffer::Capacity;
+pub use wrap::Wrap;
+pub use filter::Filter;
+pub use timestamps::Timestamps;
+pub use progress::ProgressBar;
+pub use chart::{Chart, ChartKind};
+pub use panel::Panel;
+pub use table::{Align, Column, Table};
+pub use search::{Pattern, Search};
 use crate::colors;
+use crate::sink::Sink;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    fn text(rows: Vec<Vec<Cell>>) -> Vec<String> {
+        rows.into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.0).collect())
+            .collect()
+    }
     #[test]
+    #[allow(clippy::useless_format)]
     fn create_window() {
         let mut window = Window::new(0);
         window.print(&format!("Hello World"));
         for text in window.buffer.iter() {
-            assert_eq!(text, &Some(String::from("Hello World")));
+            assert_eq!(text.cells().into_iter().map(|cell| cell.0).collect::<String>(), String::from("Hello World"));
+        }
+    }
+    #[test]
+    fn follow_tail() {
+        let mut window = Window::new(0);
+        window.height = 2;
+        for i in 0..5 {
+            window.print(&i.to_string());
+            window.flush();
+        }
+        assert_eq!(text(window.rows()), vec!["3", "4"]);
+
+        // New lines don't move the view while scrolled back
+        window.scroll_up(10);
+        assert_eq!(text(window.rows()), vec!["0", "1"]);
+        window.print("5");
+        window.flush();
+        assert_eq!(text(window.rows()), vec!["0", "1"]);
+        assert_eq!(window.title(), "Window 0 ID: 0 [+1 new]");
+
+        // Reaching the bottom follows the tail again
+        window.scroll_down(10);
+        assert_eq!(text(window.rows()), vec!["4", "5"]);
+        assert_eq!(window.title(), "Window 0 ID: 0");
+    }
+    #[test]
+    fn search_jumps() {
+        let mut window = Window::new(0);
+        window.height = 2;
+        for line in ["error 1", "ok", "error 2", "ok", "ok"] {
+            window.print(line);
+            window.flush();
+        }
+        let search = Search::new(&Pattern::Text("error".to_string()), Style::default()).unwrap();
+        assert_eq!(window.search(search), 2);
+        assert_eq!(text(window.rows()), vec!["ok", "error 2"]);
+        assert_eq!(window.title(), "Window 0 ID: 0 [2 matches]");
+        window.previous_match();
+        assert_eq!(text(window.rows()), vec!["error 1", "ok"]);
+        // There are no more matches above
+        window.previous_match();
+        assert_eq!(window.scroll, 3);
+        window.next_match();
+        assert_eq!(window.scroll, 2);
+    }
+    #[test]
+    fn counts_with_capacity() {
+        let mut window = Window::new(0);
+        window.height = 2;
+        window.set_capacity(Capacity::Lines(3));
+        let search = Search::new(&Pattern::Text("error".to_string()), Style::default()).unwrap();
+        window.search(search);
+        window.set_filter(Some(Filter::new(None, Some("skip")).unwrap()));
+        window.print("error 1\nok\nerror 2\n");
+        assert_eq!(window.title(), "Window 0 ID: 0 [filtered] [2 matches]");
+        // Dropped lines are not counted anymore, new lines dropped right away never were
+        window.print("error 4\nok\nerror 5\nskip error\n");
+        assert_eq!((window.shown, window.matched), (2, 1));
+        assert_eq!(text(window.rows()), vec!["ok", "error 5"]);
+        window.set_filter(None);
+        assert_eq!((window.shown, window.matched), (3, 2));
+    }
+    #[test]
+    fn filtered_lines() {
+        let mut window = Window::new(0);
+        window.height = 2;
+        for line in ["error 1", "ok", "warning", "ok", "error 2"] {
+            window.print(line);
+            window.flush();
         }
+        window.set_filter(Some(Filter::new(Some("error|warning"), Some("1")).unwrap()));
+        assert_eq!(text(window.rows()), vec!["warning", "error 2"]);
+        assert_eq!(window.title(), "Window 0 ID: 0 [filtered]");
+        window.scroll_up(10);
+        assert_eq!(window.scroll, 0);
+
+        // Lines that don't pass the filter don't move the view
+        window.set_filter(Some(Filter::new(Some("error|warning"), None).unwrap()));
+        window.scroll_up(1);
+        window.print("ok");
+        window.flush();
+        assert_eq!(window.new_lines, 0);
+        assert_eq!(text(window.rows()), vec!["error 1", "warning"]);
+
+        // The whole history is displayed again once disabled
+        window.set_filter_enabled(false);
+        assert_eq!(text(window.rows()), vec!["error 2", "ok"]);
+    }
+    #[test]
+    fn canvas_rows() {
+        let mut window = Window::new(0);
+        window.height = 4;
+        window.print("log line");
+        window.flush();
+        window.set_line(1, "status\nok");
+        assert_eq!(text(window.rows()), vec!["", "status", "ok", ""]);
+        window.replace_contents(&["first"]);
+        assert_eq!(text(window.rows()), vec!["first", "", "", ""]);
+        window.clear();
+        assert_eq!(text(window.rows()), vec!["", "", "", ""]);
+    }
+    #[test]
+    fn progress_rows() {
+        let mut window = Window::new(0);
+        window.height = 3;
+        window.width = 20;
+        window.set_progress(1, 7);
+        window.set_progress_bar(0, ProgressBar::new("build").total(4).ascii(true));
+        window.set_progress(0, 1);
+        assert_eq!(text(window.rows()), vec!["build [---] 1/4  25%", "7", ""]);
+        window.clear();
+        assert_eq!(text(window.rows()), vec!["", "", ""]);
+    }
+    #[test]
+    fn chart_contents() {
+        let mut window = Window::new(0);
+        window.set_chart(Chart::new(ChartKind::Bars));
+        window.push_sample(1.0);
+        window.push_sample(2.0);
+        // Never rendered
+        let contents: Vec<String> = window.contents().iter().map(Line::text).collect();
+        assert_eq!(contents.len(), 2);
+        assert!(contents[1].starts_with("2 ████"));
+        window.height = 1;
+        window.width = 10;
+        assert_eq!(window.contents().len(), 1);
+    }
+    #[test]
+    fn wrapped_rows() {
+        let mut window = Window::new(0);
+        window.height = 3;
+        window.width = 4;
+        window.wrap = Wrap::Char;
+        for line in ["abcdef", "ghijkl"] {
+            window.print(line);
+            window.flush();
+        }
+        assert_eq!(text(window.rows()), vec!["ef", "ghij", "kl"]);
+        // The oldest line can be reached at the top
+        window.scroll_up(10);
+        assert_eq!(text(window.rows()), vec!["abcd", "ef"]);
     }
 }
 
+// Size charts are exported at before the window is ever rendered
+const EXPORT_SIZE: (usize, usize) = (80, 8);
+
 // Enum to indicate windows partition
 #[derive(Debug)]
 pub enum Priority {
@@ -22,6 +200,25 @@ pub enum Priority {
     Horizontal,
 }
 
+// Content displayed by a window
+#[derive(Debug)]
+pub enum Mode {
+    // Lines printed to the window
+    Log,
+    // Screen of a program running in a pseudo-terminal
+    Terminal(Session),
+    // Rows written at random positions
+    Canvas(Vec<Line>),
+    // Progress bars, one per row
+    Progress(Vec<Option<ProgressBar>>),
+    // Rows under a header
+    Table(Table),
+    // Numbers drawn as a chart
+    Chart(Chart),
+    // Values by key, grouped into sections
+    Panel(Panel),
+}
+
 // Windows object
 // Children contain other other window ids
 // TODO Use Rc pointer for children
@@ -35,6 +232,35 @@ pub struct Window {
     pub left_child: Option<usize>,
     pub down_child: Option<usize>,
     pub priority: Option<Priority>,
+    pub visible: bool,
+    // Lines flushed while hidden
+    pub unread: usize,
+    // Lines between the bottom of the window and the last line, 0 follows the tail
+    pub scroll: usize,
+    // Lines flushed while scrolled back
+    pub new_lines: usize,
+    // Rows of text shown on last output
+    pub height: usize,
+    // Columns of text shown on last output
+    pub width: usize,
+    pub wrap: Wrap,
+    pub mode: Mode,
+    // Text displayed in the title, like the exit status of a command
+    pub status: Option<String>,
+    // Matches are highlighted and counted in the title
+    search: Option<Search>,
+    filter: Option<Filter>,
+    // Flushed lines allowed by the filter, and the ones that also match the search. They are kept up
+    // to date as lines are flushed and dropped, so rendering doesn't go through every line
+    shown: usize,
+    matched: usize,
+    pub timestamps: Timestamps,
+    // Creation of the screen, for relative timestamps
+    pub start: SystemTime,
+    // Files where flushed lines are also written
+    pub sink: Option<Sink>,
+    // Screen-wide file, lines are written with the name of the window
+    pub screen_sink: Option<Arc<Mutex<Sink>>>,
 }
 
 // TODO allow user to set Window name
@@ -48,13 +274,451 @@ impl Window {
             left_child: None,
             down_child: None,
             priority: None,
+            visible: true,
+            unread: 0,
+            scroll: 0,
+            new_lines: 0,
+            height: 0,
+            width: 0,
+            wrap: Wrap::Truncate,
+            mode: Mode::Log,
+            status: None,
+            search: None,
+            filter: None,
+            shown: 0,
+            matched: 0,
+            timestamps: Timestamps::Off,
+            start: SystemTime::now(),
+            sink: None,
+            screen_sink: None,
         }
     }
     pub fn print(&mut self, line: &str) {
-        self.buffer.append(line);
+        let lines = self.buffer.append(line);
+        self.flushed(lines);
+    }
+    pub fn println_styled(&mut self, line: &Line) {
+        let lines = self.buffer.append_line(line);
+        self.flushed(lines);
+    }
+    pub fn print_styled(&mut self, line: &str, style: Style) {
+        let lines = self.buffer.append_styled(line, style);
+        self.flushed(lines);
     }
     pub fn flush(&mut self) {
         self.buffer.flush();
+        self.flushed(1);
+    }
+    // Rows of the canvas, switching the window to canvas mode if needed
+    fn canvas(&mut self) -> &mut Vec<Line> {
+        if !matches!(self.mode, Mode::Canvas(_)) {
+            self.mode = Mode::Canvas(vec![]);
+        }
+        match &mut self.mode {
+            Mode::Canvas(rows) => rows,
+            _ => unreachable!(),
+        }
+    }
+    // Progress bars, switching the window to progress mode if needed. There is room for `bar`
+    fn progress(&mut self, bar: usize) -> &mut Option<ProgressBar> {
+        if !matches!(self.mode, Mode::Progress(_)) {
+            self.mode = Mode::Progress(vec![]);
+        }
+        match &mut self.mode {
+            Mode::Progress(bars) => {
+                if bars.len() <= bar {
+                    bars.resize(bar + 1, None);
+                }
+                &mut bars[bar]
+            }
+            _ => unreachable!(),
+        }
+    }
+    // Display `progress` in the row of `bar`
+    pub fn set_progress_bar(&mut self, bar: usize, progress: ProgressBar) {
+        *self.progress(bar) = Some(progress);
+    }
+    // Update the value of `bar`, creating it without a label if needed
+    pub fn set_progress(&mut self, bar: usize, value: u64) {
+        let now = self.buffer.now();
+        self.progress(bar).get_or_insert_with(|| ProgressBar::new("")).set(value, now);
+    }
+    // Rows of the progress bars, as wide as the window
+    fn progress_rows(&self, bars: &[Option<ProgressBar>]) -> Vec<Line> {
+        let now = self.buffer.now();
+        bars.iter()
+            .map(|bar| bar.as_ref().map(|bar| bar.render(self.width, now)).unwrap_or_default())
+            .collect()
+    }
+    // Table, switching the window to table mode if needed
+    fn table(&mut self) -> &mut Table {
+        if !matches!(self.mode, Mode::Table(_)) {
+            self.mode = Mode::Table(Table::default());
+        }
+        match &mut self.mode {
+            Mode::Table(table) => table,
+            _ => unreachable!(),
+        }
+    }
+    // Replace the table with an empty one with `columns`
+    pub fn set_table(&mut self, columns: Vec<Column>) {
+        self.mode = Mode::Table(Table::new(columns));
+    }
+    pub fn set_row(&mut self, key: &str, values: &[&str]) {
+        self.table().set_row(key, values);
+    }
+    pub fn remove_row(&mut self, key: &str) {
+        self.table().remove_row(key);
+    }
+    pub fn sort_table(&mut self, column: Option<usize>, descending: bool) {
+        self.table().sort(column, descending);
+    }
+    // Chart, switching the window to a sparkline if needed
+    fn chart(&mut self) -> &mut Chart {
+        if !matches!(self.mode, Mode::Chart(_)) {
+            self.mode = Mode::Chart(Chart::new(ChartKind::Sparkline));
+        }
+        match &mut self.mode {
+            Mode::Chart(chart) => chart,
+            _ => unreachable!(),
+        }
+    }
+    // Draw the samples with `chart`, keeping the ones the window has
+    pub fn set_chart(&mut self, chart: Chart) {
+        self.chart().replace(chart);
+    }
+    pub fn push_sample(&mut self, sample: f64) {
+        self.chart().push(sample);
+    }
+    // Status panel, switching the window to a panel if needed
+    fn panel(&mut self) -> &mut Panel {
+        if !matches!(self.mode, Mode::Panel(_)) {
+            self.mode = Mode::Panel(Panel::default());
+        }
+        match &mut self.mode {
+            Mode::Panel(panel) => panel,
+            _ => unreachable!(),
+        }
+    }
+    pub fn set_status_value(&mut self, key: &str, value: &str, style: Style) {
+        self.panel().set(key, value, style);
+    }
+    pub fn remove_status_value(&mut self, key: &str) {
+        self.panel().remove(key);
+    }
+    // Write `text` in `row`, text after a new line character goes to the next rows
+    pub fn set_line(&mut self, row: usize, text: &str) {
+        let lines: Vec<Line> = text.split('\n').map(|line| self.buffer.parse(line)).collect();
+        let canvas = self.canvas();
+        if canvas.len() < row + lines.len() {
+            canvas.resize(row + lines.len(), Line::default());
+        }
+        for (i, line) in lines.into_iter().enumerate() {
+            canvas[row + i] = line;
+        }
+    }
+    pub fn replace_contents(&mut self, lines: &[&str]) {
+        let lines = lines
+            .iter()
+            .flat_map(|line| line.split('\n'))
+            .map(|line| self.buffer.parse(line))
+            .collect();
+        *self.canvas() = lines;
+    }
+    pub fn set_capacity(&mut self, capacity: Capacity) {
+        self.buffer.set_capacity(capacity);
+        self.recount();
+    }
+    // Remove the content of the window
+    pub fn clear(&mut self) {
+        match &mut self.mode {
+            Mode::Canvas(rows) => rows.clear(),
+            Mode::Progress(bars) => bars.clear(),
+            Mode::Table(table) => table.clear(),
+            Mode::Chart(chart) => chart.clear(),
+            Mode::Panel(panel) => panel.clear(),
+            Mode::Terminal(session) => {
+                session.clear();
+                self.buffer.clear();
+            }
+            Mode::Log => self.buffer.clear(),
+        }
+        self.scroll = 0;
+        self.new_lines = 0;
+        self.recount();
+    }
+    // If `line` is displayed with the filter
+    fn allows(&self, line: &Line) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter.allows(line))
+    }
+    // If `line` is displayed and matches the search
+    fn is_match(&self, line: &Line) -> bool {
+        self.search.as_ref().is_some_and(|search| search.is_match(line)) && self.allows(line)
+    }
+    // Count the displayed lines and the matches again, after the filter or the search changed
+    fn recount(&mut self) {
+        self.buffer.take_evicted();
+        self.shown = self.buffer.iter().filter(|line| self.allows(line)).count();
+        self.matched = match self.search {
+            Some(_) => self.buffer.iter().filter(|line| self.is_match(line)).count(),
+            None => 0,
+        };
+    }
+    // Keep track of new lines
+    fn flushed(&mut self, lines: usize) {
+        let evicted = self.buffer.take_evicted();
+        self.write_sinks(&evicted, lines);
+        // Lines dropped to fit the capacity were counted, unless they are new lines dropped right away
+        let kept = self.buffer.iter().len();
+        let counted = evicted.len().min((evicted.len() + kept).saturating_sub(lines));
+        for line in &evicted[..counted] {
+            self.shown = self.shown.saturating_sub(self.allows(line) as usize);
+            self.matched = self.matched.saturating_sub(self.is_match(line) as usize);
+        }
+        if self.search.is_some() {
+            self.matched += self.buffer.iter().rev().take(lines).filter(|line| self.is_match(line)).count();
+        }
+        // Lines hidden by the filter are not new for the user
+        let lines = self.buffer.iter().rev().take(lines).filter(|line| self.allows(line)).count();
+        self.shown += lines;
+        if !self.visible {
+            self.unread += lines;
+        }
+        // Keep the view in place while scrolled back
+        if self.scroll > 0 {
+            self.scroll += lines;
+            self.new_lines += lines;
+        }
+    }
+    // Write the last `lines` lines to the files, including the ones `evicted` to fit the capacity
+    fn write_sinks(&mut self, evicted: &[Line], lines: usize) {
+        if self.sink.is_none() && self.screen_sink.is_none() {
+            return;
+        }
+        let skip = (evicted.len() + self.buffer.iter().len()).saturating_sub(lines);
+        for line in evicted.iter().chain(self.buffer.iter()).skip(skip) {
+            if let Some(sink) = &mut self.sink {
+                sink.write(None, line);
+            }
+            if let Some(sink) = &self.screen_sink {
+                sink.lock().unwrap().write(Some(&self.name), line);
+            }
+        }
+    }
+    // Split line into the rows it takes in the window, after its timestamp
+    fn wrap_line(&self, line: &Line, previous: Option<&Line>) -> Vec<Vec<Cell>> {
+        let blank = (' ', Style::default());
+        let mut cells = match &self.search {
+            Some(search) => search.highlight(line),
+            None => line.cells(),
+        };
+        let prefix = line
+            .time
+            .and_then(|time| self.timestamps.prefix(time, previous.and_then(|line| line.time), self.start));
+        if let Some(prefix) = prefix {
+            let style = Style { dim: true, ..Style::default() };
+            let prefix = prefix.chars().chain([' ']).map(|c| (c, style));
+            cells.splice(0..0, prefix);
+        }
+        wrap::wrap(&cells, self.width, self.wrap, |c| c.0.is_whitespace(), blank)
+    }
+    // Lines allowed by the filter, newest first
+    fn shown_rev(&self) -> impl Iterator<Item = &Line> + '_ {
+        self.buffer.iter().rev().filter(|line| self.allows(line))
+    }
+    // Replace the filter, and go back to the tail since the scroll position refers to other lines
+    pub fn set_filter(&mut self, filter: Option<Filter>) {
+        self.filter = filter;
+        self.scroll = 0;
+        self.new_lines = 0;
+        self.recount();
+    }
+    pub fn set_filter_enabled(&mut self, enabled: bool) {
+        if let Some(filter) = &mut self.filter {
+            filter.enabled = enabled;
+            self.scroll = 0;
+            self.new_lines = 0;
+            self.recount();
+        }
+    }
+    // Furthest the window can scroll back, so the oldest line is at the top of the window
+    fn max_scroll(&self) -> usize {
+        if self.wrap == Wrap::Truncate {
+            return self.shown.saturating_sub(self.height);
+        }
+        let mut rows = 0;
+        let mut previous = None;
+        for (i, line) in self.buffer.iter().filter(|line| self.allows(line)).enumerate() {
+            rows += self.wrap_line(line, previous).len();
+            // If the line doesn't fit, stop before it so the top of the oldest line is shown
+            if rows == self.height {
+                return self.shown - i - 1;
+            } else if rows > self.height {
+                return self.shown - i.max(1);
+            }
+            previous = Some(line);
+        }
+        0
+    }
+    // Scroll position clamped to the oldest line
+    fn bottom(&self) -> usize {
+        match self.scroll {
+            0 => 0,
+            scroll => scroll.min(self.max_scroll()),
+        }
+    }
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_add(lines).min(self.max_scroll());
+    }
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines).min(self.max_scroll());
+        // Follow the tail again once at the bottom
+        if self.scroll == 0 {
+            self.new_lines = 0;
+        }
+    }
+    // Lines above the newest displayed line of the first match at or above `offset`, walking back
+    fn match_above(&self, offset: usize) -> Option<usize> {
+        let search = self.search.as_ref()?;
+        self.shown_rev().enumerate().skip(offset).find(|(_, line)| search.is_match(line)).map(|(i, _)| i)
+    }
+    // Scroll so the line `offset` lines above the newest one is at the bottom of the window, or as low as possible
+    fn scroll_to(&mut self, offset: usize) {
+        self.scroll = offset.min(self.max_scroll());
+        if self.scroll == 0 {
+            self.new_lines = 0;
+        }
+    }
+    // Start a search, and go to the last match at or above the bottom of the window. Returns the amount of matches
+    pub fn search(&mut self, search: Search) -> usize {
+        self.search = Some(search);
+        self.recount();
+        if let Some(offset) = self.match_above(self.bottom()) {
+            self.scroll_to(offset);
+        }
+        self.matched
+    }
+    // Stop highlighting and counting the matches
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.matched = 0;
+    }
+    // Go to the first match below the bottom of the window
+    pub fn next_match(&mut self) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        // Only the lines below the bottom are walked, the closest one is the last
+        let offset = (self.shown_rev().take(self.bottom()).enumerate())
+            .filter(|(_, line)| search.is_match(line))
+            .last()
+            .map(|(i, _)| i);
+        if let Some(offset) = offset {
+            self.scroll_to(offset);
+        }
+    }
+    // Go to the last match above the bottom of the window
+    pub fn previous_match(&mut self) {
+        if let Some(offset) = self.match_above(self.bottom() + 1) {
+            self.scroll_to(offset);
+        }
+    }
+    // Rows of text shown in the window, the last line at the scroll position goes last
+    pub fn rows(&self) -> Vec<Vec<Cell>> {
+        match &self.mode {
+            Mode::Terminal(session) => return session.rows(self.width, self.height),
+            Mode::Canvas(lines) => {
+                let mut rows: Vec<Vec<Cell>> = lines.iter().take(self.height).map(Line::cells).collect();
+                rows.resize(self.height, vec![]);
+                return rows;
+            }
+            Mode::Progress(bars) => {
+                let mut rows: Vec<Vec<Cell>> = self.progress_rows(bars).iter().take(self.height).map(Line::cells).collect();
+                rows.resize(self.height, vec![]);
+                return rows;
+            }
+            Mode::Table(table) => {
+                let mut rows: Vec<Vec<Cell>> = table.render(self.width).iter().take(self.height).map(Line::cells).collect();
+                rows.resize(self.height, vec![]);
+                return rows;
+            }
+            Mode::Chart(chart) => {
+                let mut rows: Vec<Vec<Cell>> = chart.render(self.width, self.height).iter().map(Line::cells).collect();
+                rows.resize(self.height, vec![]);
+                return rows;
+            }
+            Mode::Panel(panel) => {
+                let mut rows: Vec<Vec<Cell>> = panel.render().iter().take(self.height).map(Line::cells).collect();
+                rows.resize(self.height, vec![]);
+                return rows;
+            }
+            Mode::Log => {}
+        }
+        let bottom = self.bottom();
+        // The line that hasn't been flushed is shown while following the tail
+        let pending = match bottom {
+            0 => self.buffer.pending().filter(|line| self.allows(line)),
+            _ => None,
+        };
+        // Walk back from the bottom, only through the lines that are visible
+        let mut lines = pending.iter().chain(self.shown_rev().skip(bottom)).peekable();
+        let mut rows = vec![];
+        while rows.len() < self.height {
+            let Some(line) = lines.next() else {
+                break;
+            };
+            rows.extend(self.wrap_line(line, lines.peek().copied()).into_iter().rev());
+        }
+        rows.truncate(self.height);
+        rows.reverse();
+        rows
+    }
+    // Every line the window has, or the rows of its screen
+    pub fn contents(&self) -> Vec<Line> {
+        match &self.mode {
+            Mode::Log => self.buffer.iter().cloned().chain(self.buffer.pending()).collect(),
+            Mode::Canvas(lines) => lines.clone(),
+            Mode::Progress(bars) => self.progress_rows(bars),
+            // Columns are not truncated
+            Mode::Table(table) => table.render(usize::MAX),
+            Mode::Chart(chart) => match self.width > 0 && self.height > 0 {
+                true => chart.render(self.width, self.height),
+                false => chart.render(EXPORT_SIZE.0, EXPORT_SIZE.1),
+            },
+            Mode::Panel(panel) => panel.render(),
+            Mode::Terminal(session) => {
+                let blank = (' ', Style::default());
+                let rows = session.cells().into_iter().map(|mut row| {
+                    while row.last() == Some(&blank) {
+                        row.pop();
+                    }
+                    row.into_iter().collect()
+                });
+                rows.collect()
+            }
+        }
+    }
+    // Text at the bottom of the window
+    pub fn title(&self) -> String {
+        let mut title = format!("{} ID: {}", self.get_name(), self.id);
+        if let Some(status) = &self.status {
+            title.push_str(&format!(" [{}]", status));
+        }
+        if let Mode::Chart(chart) = &self.mode {
+            if let Some(summary) = chart.summary() {
+                title.push_str(&format!(" [{}]", summary));
+            }
+        }
+        if self.new_lines > 0 {
+            title.push_str(&format!(" [+{} new]", self.new_lines));
+        }
+        if self.filter.as_ref().is_some_and(|filter| filter.enabled) {
+            title.push_str(" [filtered]");
+        }
+        if self.search.is_some() {
+            title.push_str(&format!(" [{} matches]", self.matched));
+        }
+        title
     }
     pub fn get_id(&self) -> usize {
         self.id
@@ -0,0 +1,255 @@
+use crate::colors::{self, Color, Style};
+
+#[cfg(test)]
+mod ansi_tests {
+    use super::*;
+    fn parse(parser: &mut Parser, text: &str) -> Vec<Action> {
+        text.chars().filter_map(|c| parser.advance(c)).collect()
+    }
+    #[test]
+    fn sequences() {
+        let mut parser = Parser::new();
+        let actions = parse(&mut parser, "a\x1b[1;31mb\r\x1b]0;title\x07\x1b(Bc\x1b[?25l");
+        assert_eq!(
+            actions,
+            vec![
+                Action::Print('a'),
+                Action::Csi(Csi { private: None, params: vec![1, 31], action: 'm' }),
+                Action::Print('b'),
+                Action::Control('\r'),
+                Action::Esc { intermediate: Some('('), action: 'B' },
+                Action::Print('c'),
+                Action::Csi(Csi { private: Some('?'), params: vec![25], action: 'l' }),
+            ]
+        );
+    }
+    #[test]
+    fn split_sequence() {
+        let mut parser = Parser::new();
+        assert_eq!(parse(&mut parser, "\x1b[3"), vec![]);
+        assert_eq!(
+            parse(&mut parser, "2m"),
+            vec![Action::Csi(Csi { private: None, params: vec![32], action: 'm' })]
+        );
+    }
+    #[test]
+    fn sgr() {
+        let mut style = Style::default();
+        apply_sgr(&mut style, &[1, 4, 38, 5, 196, 42]);
+        assert_eq!(style.fg, Color::LightRed);
+        assert_eq!(style.bg, Color::Green);
+        assert!(style.bold && style.underline);
+        apply_sgr(&mut style, &[22, 39, 48, 2, 0, 0, 0]);
+        assert_eq!(style.fg, Color::Null);
+        assert_eq!(style.bg, Color::Black);
+        assert!(!style.bold && style.underline);
+        apply_sgr(&mut style, &[]);
+        assert_eq!(style, Style::default());
+    }
+}
+
+// Control sequence introduced by `ESC [`
+#[derive(PartialEq, Clone, Debug)]
+pub struct Csi {
+    // Private marker such as `?` in `ESC [ ? 25 l`
+    pub private: Option<char>,
+    pub params: Vec<u16>,
+    pub action: char,
+}
+
+impl Csi {
+    // Parameter at `index`, missing or zero parameters take the `default` value
+    pub fn param(&self, index: usize, default: u16) -> u16 {
+        match self.params.get(index) {
+            Some(0) | None => default,
+            Some(&value) => value,
+        }
+    }
+}
+
+// What a character means once parsed
+#[derive(PartialEq, Clone, Debug)]
+pub enum Action {
+    // Printable character
+    Print(char),
+    // C0 control character, like `\r` or `\n`
+    Control(char),
+    Csi(Csi),
+    // Escape sequence that is not a control sequence, like `ESC 7`
+    Esc { intermediate: Option<char>, action: char },
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum State {
+    Ground,
+    Escape,
+    Intermediate(char),
+    Csi,
+    // Operating system command and other strings, ignored up to the string terminator
+    Text,
+    TextEscape,
+}
+
+// ANSI escape sequences parser. It keeps state between calls so sequences can be split
+#[derive(Debug)]
+pub struct Parser {
+    state: State,
+    private: Option<char>,
+    params: String,
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        Parser {
+            state: State::Ground,
+            private: None,
+            params: String::new(),
+        }
+    }
+    // Feed one character, returns its meaning once the sequence is complete
+    pub fn advance(&mut self, c: char) -> Option<Action> {
+        match (self.state, c) {
+            // Cancel sequence
+            (_, '\x18' | '\x1a') => {
+                self.state = State::Ground;
+                None
+            }
+            (State::Text, '\x07' | '\u{9c}') => {
+                self.state = State::Ground;
+                None
+            }
+            (State::Text, '\x1b') => {
+                self.state = State::TextEscape;
+                None
+            }
+            (State::Text, _) => None,
+            (State::TextEscape, '\\') => {
+                self.state = State::Ground;
+                None
+            }
+            (State::TextEscape, _) => {
+                self.state = State::Text;
+                None
+            }
+            (_, '\x1b') => {
+                self.state = State::Escape;
+                None
+            }
+            (_, '\u{9b}') => {
+                self.start_csi();
+                None
+            }
+            (_, '\u{9d}') => {
+                self.state = State::Text;
+                None
+            }
+            // Other C1 control characters
+            (_, '\u{80}'..='\u{9f}') => None,
+            // Control characters are executed even inside sequences
+            (_, '\0'..='\x1f') => Some(Action::Control(c)),
+            (State::Ground, _) => Some(Action::Print(c)),
+            (State::Escape, '[') => {
+                self.start_csi();
+                None
+            }
+            (State::Escape, ']' | 'P' | 'X' | '^' | '_') => {
+                self.state = State::Text;
+                None
+            }
+            (State::Escape, ' '..='/') => {
+                self.state = State::Intermediate(c);
+                None
+            }
+            (State::Escape, _) => {
+                self.state = State::Ground;
+                Some(Action::Esc { intermediate: None, action: c })
+            }
+            (State::Intermediate(_), ' '..='/') => None,
+            (State::Intermediate(intermediate), _) => {
+                self.state = State::Ground;
+                Some(Action::Esc { intermediate: Some(intermediate), action: c })
+            }
+            (State::Csi, '<'..='?') if self.params.is_empty() && self.private.is_none() => {
+                self.private = Some(c);
+                None
+            }
+            (State::Csi, '0'..='?' | ' '..='/') => {
+                self.params.push(c);
+                None
+            }
+            (State::Csi, '@'..='~') => {
+                self.state = State::Ground;
+                let params = match self.params.is_empty() {
+                    true => vec![],
+                    false => self.params.split([';', ':']).map(parse_param).collect(),
+                };
+                Some(Action::Csi(Csi { private: self.private, params, action: c }))
+            }
+            // Malformed sequence
+            (State::Csi, _) => {
+                self.state = State::Ground;
+                None
+            }
+        }
+    }
+    fn start_csi(&mut self) {
+        self.state = State::Csi;
+        self.private = None;
+        self.params.clear();
+    }
+}
+
+// Parse a numeric parameter, ignoring anything that is not a digit
+fn parse_param(param: &str) -> u16 {
+    param
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0u16, |n, d| n.saturating_mul(10).saturating_add(d as u16))
+}
+
+// Read an extended color (`5;n` or `2;r;g;b`) from SGR parameters
+fn extended_color(params: &mut std::slice::Iter<u16>) -> Color {
+    match params.next() {
+        Some(5) => colors::ansi_color(params.next().copied().unwrap_or(0).min(255) as u8),
+        Some(2) => {
+            let mut next = || params.next().copied().unwrap_or(0).min(255) as u8;
+            let (r, g, b) = (next(), next(), next());
+            colors::rgb_color(r, g, b)
+        }
+        _ => Color::Null,
+    }
+}
+
+// Apply "select graphic rendition" parameters to `style`
+pub fn apply_sgr(style: &mut Style, params: &[u16]) {
+    if params.is_empty() {
+        *style = Style::default();
+    }
+    let mut params = params.iter();
+    while let Some(&param) = params.next() {
+        match param {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            7 => style.reverse = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            27 => style.reverse = false,
+            30..=37 => style.fg = colors::ansi_color(param as u8 - 30),
+            38 => style.fg = extended_color(&mut params),
+            39 => style.fg = Color::Null,
+            40..=47 => style.bg = colors::ansi_color(param as u8 - 40),
+            48 => style.bg = extended_color(&mut params),
+            49 => style.bg = Color::Null,
+            90..=97 => style.fg = colors::ansi_color(param as u8 - 90 + 8),
+            100..=107 => style.bg = colors::ansi_color(param as u8 - 100 + 8),
+            _ => {}
+        }
+    }
+}
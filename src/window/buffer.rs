@@ -1,107 +1,396 @@
+use super::ansi::{self, Action, Parser};
+use super::line::{Cell, Line};
+use crate::colors::Style;
+use crate::time::{Clock, SystemClock};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::SystemTime;
+
 // Default vertical size of Window
-// TODO make it dynamic for memory and time efficiency
 const BUFFER_SIZE: usize = 64;
-// Buffer content default value
-const INIT: Option<String> = None;
 
 #[cfg(test)]
 mod buffer_tests {
     use super::*;
+    use super::super::line::Span;
+    use crate::colors::Color;
+    fn text(line: &Line) -> String {
+        line.cells().into_iter().map(|cell| cell.0).collect()
+    }
     #[test]
     fn print_buffer() {
         let mut buffer = Buffer::new();
 
         for _ in 0..1026 {
-            buffer.append(&format!("Hello World"));
+            buffer.append("Hello World");
             buffer.flush();
         }
 
+        assert_eq!(buffer.iter().len(), BUFFER_SIZE);
         for i in buffer.iter() {
-            assert_eq!(i, &Some(String::from("Hello World")));
+            assert_eq!(text(i), String::from("Hello World"));
+        }
+    }
+    #[test]
+    fn new_lines() {
+        let mut buffer = Buffer::new();
+        assert_eq!(buffer.append("first\nsecond\nthi"), 2);
+        assert_eq!(buffer.append("rd"), 0);
+        buffer.flush();
+        assert_eq!(buffer.append("\n"), 1);
+        let lines: Vec<String> = buffer.iter().map(text).collect();
+        assert_eq!(lines, vec!["first", "second", "third", ""]);
+    }
+    #[test]
+    fn ansi_styles() {
+        let mut buffer = Buffer::new();
+        buffer.append("\x1b[1;3");
+        buffer.append("1merror\x1b[0m: \x1b]0;title\x07done\x1b[2J\n");
+        let line = buffer.iter().next().unwrap();
+        assert_eq!(text(line), "error: done");
+        assert_eq!(line.spans[0].style.fg, Color::Red);
+        assert!(line.spans[0].style.bold);
+        assert_eq!(line.spans[1].style, Style::default());
+
+        // Styles can be stripped
+        buffer.set_strip(true);
+        buffer.append("\x1b[31mplain\n");
+        assert_eq!(buffer.iter().nth(1).unwrap().spans.len(), 1);
+        assert_eq!(buffer.iter().nth(1).unwrap().spans[0].style, Style::default());
+    }
+    #[test]
+    fn arrival_times() {
+        // Every call is one second later
+        struct Ticks(std::sync::atomic::AtomicU64);
+        impl Clock for Ticks {
+            fn now(&self) -> std::time::SystemTime {
+                let secs = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs)
+            }
+        }
+        let mut buffer = Buffer::new();
+        buffer.set_clock(Arc::new(Ticks(0.into())));
+        buffer.append("first\nsec");
+        // The pending line keeps the time of its first character
+        let pending = buffer.pending().unwrap().time;
+        assert_eq!(buffer.pending().unwrap().time, pending);
+        buffer.append("ond\n");
+        let times: Vec<u64> = buffer
+            .iter()
+            .map(|line| line.time.unwrap().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs())
+            .collect();
+        assert_eq!(times, vec![0, 1]);
+        assert_eq!(buffer.iter().nth(1).unwrap().time, pending);
+    }
+    #[test]
+    fn styled_lines() {
+        let green = Style { fg: Color::Green, ..Style::default() };
+        let mut buffer = Buffer::new();
+        buffer.append("[");
+        let line = Line::new().span("OK", green).span("\x1b[31m\tdone\nnext", Style::default());
+        assert_eq!(buffer.append_line(&line), 2);
+        let lines: Vec<String> = buffer.iter().map(text).collect();
+        assert_eq!(lines, vec!["[OK[31m    done", "next"]);
+        assert_eq!(buffer.iter().next().unwrap().spans[1], Span::new("OK", green));
+    }
+    #[test]
+    fn in_place_updates() {
+        let mut buffer = Buffer::new();
+        buffer.append("\r 10%");
+        buffer.append("\r 20%");
+        assert_eq!(text(&buffer.pending().unwrap()), " 20%");
+        buffer.append("\n");
+        buffer.append("abc\x08\x08X\n");
+        buffer.append("hello\r\x1b[Kbye\n");
+        buffer.append("abcdef\r\x1b[3C\x1b[1K\n");
+        let lines: Vec<String> = buffer.iter().map(text).collect();
+        assert_eq!(lines, vec![" 20%", "aXc", "bye", "    ef"]);
+        assert_eq!(buffer.pending(), None);
+    }
+    #[test]
+    fn capacity() {
+        let mut buffer = Buffer::new();
+        buffer.set_capacity(Capacity::Unlimited);
+        for i in 0..1026 {
+            buffer.append(&i.to_string());
+            buffer.flush();
         }
+        assert_eq!(buffer.iter().len(), 1026);
+
+        // Shrinking the buffer drops the oldest lines
+        buffer.set_capacity(Capacity::Lines(10));
+        assert_eq!(buffer.iter().len(), 10);
+        assert_eq!(text(buffer.iter().next().unwrap()), String::from("1016"));
+
+        // Lines "1020" to "1025" fit in 24 bytes
+        buffer.set_capacity(Capacity::Bytes(24));
+        assert_eq!(buffer.iter().len(), 6);
+        assert_eq!(buffer.bytes(), 24);
+    }
+    #[test]
+    fn line_over_capacity() {
+        let mut buffer = Buffer::new();
+        buffer.set_capacity(Capacity::Bytes(4));
+        buffer.append("hello world\n");
+        assert_eq!(buffer.iter().len(), 1);
+        assert_eq!(text(buffer.iter().next().unwrap()), String::from("hello world"));
+        buffer.append("ab\n");
+        assert_eq!(buffer.iter().len(), 1);
+        assert_eq!(buffer.bytes(), 2);
     }
 }
 
-#[derive(Debug)]
-pub struct Buffer {
-    queue: [Option<String>; BUFFER_SIZE],
-    start_pointer: usize,
-    end_pointer: usize,
+/// `Capacity` sets how many lines a window keeps. Once it is full, the oldest lines are dropped.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Capacity};
+///
+/// let mut screen = Screen::new();
+///
+/// // Keep the last 10000 lines of window 0
+/// screen.set_window_capacity(0, Capacity::Lines(10000)).unwrap();
+///
+/// // New windows keep at most 1 MiB of text
+/// screen.set_default_capacity(Capacity::Bytes(1 << 20));
+/// ```
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Capacity {
+    /// Never drop lines.
+    Unlimited,
+    /// Keep at most this amount of lines.
+    Lines(usize),
+    /// Keep at most this amount of bytes of text.
+    Bytes(usize),
 }
 
-// Iterator for buffer
-// The pointers indicate the is content in range [start, end)
-// If iter find the end of the buffer before reaching end, it cycles to the beggining
-pub struct BufferIterator<'a> {
-    queue_pointer: &'a [Option<String>],
-    start_pointer: usize,
-    end_pointer: usize,
+impl Default for Capacity {
+    fn default() -> Self {
+        Capacity::Lines(BUFFER_SIZE)
+    }
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    // Flushed lines, oldest first
+    queue: VecDeque<Line>,
+    // Line that hasn't been flushed yet, and the position where the next character is written
+    current: Vec<Cell>,
+    cursor: usize,
+    capacity: Capacity,
+    // Bytes used by flushed lines
+    bytes: usize,
+    // Escape sequences parser and the style they set
+    parser: Parser,
+    style: Style,
+    // Ignore styles set by escape sequences
+    strip: bool,
+    // Arrival time of lines
+    clock: Arc<dyn Clock>,
+    // When the first character of the current line was written
+    started: Option<SystemTime>,
+    // Lines dropped to fit the capacity, until they are taken
+    evicted: Vec<Line>,
 }
 
 impl Buffer {
     // Returna new empty buffer
     pub fn new() -> Buffer {
         Buffer {
-            queue: [INIT; BUFFER_SIZE],
-            start_pointer: 0,
-            end_pointer: 0,
+            queue: VecDeque::new(),
+            current: vec![],
+            cursor: 0,
+            capacity: Capacity::default(),
+            bytes: 0,
+            parser: Parser::new(),
+            style: Style::default(),
+            strip: false,
+            clock: Arc::new(SystemClock),
+            started: None,
+            evicted: vec![],
         }
     }
-    // TODO handle other non-printable characters
-    // TODO Make new line characters input new lines in the buffer (insert in queue)
-    fn proccess_str(raw: &str) -> String {
-        // Proccessed string
-        let mut pro = String::new();
-        for letter in raw.chars() {
-            match letter {
-                // Ignore new line or null characters (it screws with the printing proccess)
-                '\n' | '\0' => {},
+    // Append to current line, every new line character flushes it. Returns the amount of flushed lines
+    pub fn append(&mut self, suffix: &str) -> usize {
+        let mut lines = 0;
+        for letter in suffix.chars() {
+            match self.parser.advance(letter) {
+                Some(Action::Print(letter)) => self.write(letter, self.style),
+                Some(Action::Control('\n')) => {
+                    self.flush();
+                    lines += 1;
+                }
                 // Reduce tab size
-                '\t' => { pro.push_str("    "); },
-                // Resets the string
-                '\r' => { pro = String::new(); },
-                letter => { pro.push(letter); },
-            };
+                Some(Action::Control('\t')) => {
+                    for _ in 0..4 {
+                        self.write(' ', self.style);
+                    }
+                }
+                // Go back to the start of the line, next characters overwrite it
+                Some(Action::Control('\r')) => self.cursor = 0,
+                Some(Action::Control('\x08')) => self.cursor = self.cursor.saturating_sub(1),
+                Some(Action::Csi(csi)) if csi.private.is_none() => {
+                    let n = csi.param(0, 1) as usize;
+                    match csi.action {
+                        'm' if !self.strip => ansi::apply_sgr(&mut self.style, &csi.params),
+                        'C' => self.cursor += n,
+                        'D' => self.cursor = self.cursor.saturating_sub(n),
+                        'G' => self.cursor = n - 1,
+                        'K' => self.erase(csi.params.first().copied().unwrap_or(0)),
+                        _ => {}
+                    }
+                }
+                // Ignore other non-printable characters and sequences (they screw with the printing proccess)
+                _ => {}
+            }
+        }
+        lines
+    }
+    // Write character at the cursor
+    fn write(&mut self, letter: char, style: Style) {
+        if self.started.is_none() {
+            self.started = Some(self.clock.now());
+        }
+        if self.cursor < self.current.len() {
+            self.current[self.cursor] = (letter, style);
+        } else {
+            self.current.resize(self.cursor, (' ', Style::default()));
+            self.current.push((letter, style));
+        }
+        self.cursor += 1;
+    }
+    // Erase from the cursor to the end of the line (0), from the start to the cursor (1), or the whole line (2)
+    fn erase(&mut self, mode: u16) {
+        let blank = (' ', Style::default());
+        match mode {
+            0 => self.current.truncate(self.cursor),
+            1 => {
+                let end = (self.cursor + 1).min(self.current.len());
+                self.current[..end].fill(blank);
+            }
+            _ => self.current.clear(),
         }
-        pro
     }
-    // Append to line at the top or initiate new line if empty
-    pub fn append(&mut self, suffix: &str) {
-        match &mut self.queue[self.end_pointer] {
-            Some(line) => line.push_str(&Self::proccess_str(suffix)),
-            None => self.queue[self.end_pointer] = Some(Self::proccess_str(suffix)),
+    // Append text with `style` as the starting style
+    pub fn append_styled(&mut self, suffix: &str, style: Style) -> usize {
+        let previous = std::mem::replace(&mut self.style, style);
+        let lines = self.append(suffix);
+        self.style = previous;
+        lines
+    }
+    // Append a styled line to the current one and flush it. Its text is not parsed, but new line
+    // characters flush and other control characters are ignored. Returns the amount of flushed lines
+    pub fn append_line(&mut self, line: &Line) -> usize {
+        let mut lines = 1;
+        for (letter, style) in line.cells() {
+            match letter {
+                '\n' => {
+                    self.flush();
+                    lines += 1;
+                }
+                '\t' => {
+                    for _ in 0..4 {
+                        self.write(' ', style);
+                    }
+                }
+                letter if letter.is_control() => {}
+                letter => self.write(letter, style),
+            }
         }
+        self.flush();
+        lines
     }
     // Flush content in current line, go to next one
     pub fn flush(&mut self) {
-        self.end_pointer = (self.end_pointer + 1) % BUFFER_SIZE;
-        // If buffer is full, delete line at front of queue
-        if self.start_pointer == self.end_pointer {
-            self.queue[self.end_pointer] = None;
-            self.start_pointer = (self.start_pointer + 1) % BUFFER_SIZE;
+        let mut line: Line = std::mem::take(&mut self.current).into_iter().collect();
+        // Lines arrive when their first character is written
+        line.time = Some(self.started.take().unwrap_or_else(|| self.clock.now()));
+        self.cursor = 0;
+        self.bytes += line.len();
+        self.queue.push_back(line);
+        self.shrink();
+    }
+    // Drop lines at front of queue until buffer fits its capacity. The newest line is kept even if
+    // it is bigger than the bytes allowed, so flushed lines can always be read
+    fn shrink(&mut self) {
+        loop {
+            let full = match self.capacity {
+                Capacity::Unlimited => false,
+                Capacity::Lines(lines) => self.queue.len() > lines,
+                Capacity::Bytes(bytes) => self.bytes > bytes && self.queue.len() > 1,
+            };
+            if !full {
+                break;
+            }
+            match self.queue.pop_front() {
+                Some(line) => {
+                    self.bytes -= line.len();
+                    self.evicted.push(line);
+                }
+                None => break,
+            }
         }
     }
-    // Return buffer iterator
-    pub fn iter(&self) -> BufferIterator {
-        BufferIterator {
-            queue_pointer: &self.queue,
-            start_pointer: self.start_pointer,
-            end_pointer: self.end_pointer,
+    // Parse a line of text with escape sequences, the same way it would be printed
+    pub fn parse(&self, text: &str) -> Line {
+        let mut buffer = Buffer::new();
+        buffer.set_strip(self.strip);
+        buffer.append(text);
+        buffer.pending().unwrap_or_default()
+    }
+    // Lines dropped since the last call, oldest first
+    pub fn take_evicted(&mut self) -> Vec<Line> {
+        std::mem::take(&mut self.evicted)
+    }
+    // Remove all lines
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.evicted.clear();
+        self.current.clear();
+        self.started = None;
+        self.cursor = 0;
+        self.bytes = 0;
+    }
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+    // Current time of the clock
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+    pub fn set_strip(&mut self, strip: bool) {
+        self.strip = strip;
+        if strip {
+            self.style = Style::default();
         }
     }
-}
-
-impl<'a> Iterator for BufferIterator<'a> {
-    type Item = &'a Option<String>;
-
-    // Iterator method to cycle through buffer
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.start_pointer != self.end_pointer {
-            let borrow = &self.queue_pointer[self.start_pointer];
-            self.start_pointer = (self.start_pointer + 1) % BUFFER_SIZE;
-            Some(borrow)
-        } else {
-            None
+    pub fn set_capacity(&mut self, capacity: Capacity) {
+        self.capacity = capacity;
+        self.shrink();
+    }
+    // Bytes of text stored, including current line
+    pub fn bytes(&self) -> usize {
+        self.bytes + self.current.iter().map(|cell| cell.0.len_utf8()).sum::<usize>()
+    }
+    // Line that hasn't been flushed yet, if it has any text. Its arrival time is when its first
+    // character was written
+    pub fn pending(&self) -> Option<Line> {
+        if self.current.is_empty() {
+            return None;
         }
+        let mut line: Line = self.current.iter().copied().collect();
+        line.time = self.started;
+        Some(line)
     }
+    // Return iterator through flushed lines, oldest first
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, Line> {
+        self.queue.iter()
+    }
+}
+
+// Text without control characters nor escape sequences, which would break the row it is displayed in
+pub fn clean(text: &str) -> String {
+    let text: String = text.chars().map(|c| if c == '\n' || c == '\r' { ' ' } else { c }).collect();
+    let mut buffer = Buffer::new();
+    buffer.set_strip(true);
+    buffer.parse(&text).text()
 }
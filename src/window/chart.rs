@@ -0,0 +1,233 @@
+use super::line::Line;
+use super::progress::EIGHTHS;
+use crate::colors::{Color, Style};
+use std::collections::VecDeque;
+
+#[cfg(test)]
+mod chart_tests {
+    use super::*;
+    fn text(lines: Vec<Line>) -> Vec<String> {
+        lines.iter().map(Line::text).collect()
+    }
+    fn chart(kind: ChartKind, samples: &[f64]) -> Chart {
+        let mut chart = Chart::new(kind);
+        for &sample in samples {
+            chart.push(sample);
+        }
+        chart
+    }
+    #[test]
+    fn sparkline() {
+        let chart = chart(ChartKind::Sparkline, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        // The newest samples are on the right
+        assert_eq!(text(chart.render(10, 1)), vec!["  ▁▂▃▄▅▆▇█"]);
+        assert_eq!(text(chart.render(4, 2)), vec!["  ▃█", "▁▆██"]);
+    }
+    #[test]
+    fn braille() {
+        let chart = chart(ChartKind::Braille, &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(text(chart.render(2, 1)), vec!["⣠⣾"]);
+    }
+    #[test]
+    fn bars() {
+        let chart = chart(ChartKind::Bars, &[9.0, 2.5, 10.0]);
+        assert_eq!(text(chart.render(10, 2)), vec!["2.50 █▎", "  10 █████"]);
+    }
+    #[test]
+    fn bounded_history() {
+        let mut chart = chart(ChartKind::Sparkline, &[5.0, f64::NAN, 1.0, 3.0]).capacity(2);
+        assert_eq!(chart.summary().unwrap(), "min 1 max 3 last 3");
+        chart.push(2.0);
+        assert_eq!(chart.samples, [3.0, 2.0]);
+    }
+}
+
+// Characters of a cell filled by 1 to 8 eighths from the bottom
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// Dots of a braille character, from the bottom, for the left and the right samples
+const DOTS: [[u32; 4]; 2] = [[0x40, 0x04, 0x02, 0x01], [0x80, 0x20, 0x10, 0x08]];
+// Samples kept by default
+const SAMPLES: usize = 512;
+
+/// How a `Chart` draws its samples.
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub enum ChartKind {
+    /// One sample per column, with block characters.
+    #[default]
+    Sparkline,
+    /// Two samples per column, with braille characters.
+    Braille,
+    /// One sample per row, as a horizontal bar after its value.
+    Bars,
+}
+
+/// A `Chart` draws the numbers pushed to a window with `Screen::push_sample`, scaled to the size of
+/// the window. The newest samples are displayed, and the minimum, maximum and last value of all of
+/// them are displayed in the title.
+///
+/// ```ignore
+/// use multi_window_output::{Chart, ChartKind, Screen};
+///
+/// let mut screen = Screen::new();
+/// // Keep the latency of the last 1000 requests
+/// screen.set_chart(0, Chart::new(ChartKind::Braille).capacity(1000)).unwrap();
+/// screen.push_sample(0, 12.5).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Chart {
+    kind: ChartKind,
+    capacity: usize,
+    style: Style,
+    // Oldest first
+    samples: VecDeque<f64>,
+}
+
+impl Chart {
+    /// Create a `Chart` that keeps 512 samples.
+    pub fn new(kind: ChartKind) -> Chart {
+        Chart {
+            kind,
+            capacity: SAMPLES,
+            style: Style { fg: Color::Green, ..Style::default() },
+            samples: VecDeque::new(),
+        }
+    }
+    /// Keep at most `capacity` samples, dropping the oldest ones.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+        self
+    }
+    /// Style of the characters of the chart. Green by default.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    // Draw the samples of `chart` from now on
+    pub fn replace(&mut self, chart: Chart) {
+        let samples = std::mem::take(&mut self.samples);
+        *self = chart;
+        for sample in samples {
+            self.push(sample);
+        }
+    }
+    // Add a sample, not a number nor infinite ones are ignored
+    pub fn push(&mut self, sample: f64) {
+        if !sample.is_finite() || self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+    // Text displayed in the title of the window
+    pub fn summary(&self) -> Option<String> {
+        let last = *self.samples.back()?;
+        let min = self.samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Some(format!("min {} max {} last {}", format_value(min), format_value(max), format_value(last)))
+    }
+    // Newest `amount` samples
+    fn newest(&self, amount: usize) -> Vec<f64> {
+        self.samples.iter().skip(self.samples.len().saturating_sub(amount)).copied().collect()
+    }
+    // Height of every sample in `levels`, from 1 so the lowest ones are visible
+    fn scale(samples: &[f64], levels: usize) -> Vec<usize> {
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        (samples.iter())
+            .map(|sample| match range > 0.0 {
+                true => 1 + ((sample - min) / range * (levels - 1) as f64).round() as usize,
+                false => 1,
+            })
+            .collect()
+    }
+    // Rows of text, top first, with one sample per column
+    fn sparkline(&self, width: usize, height: usize) -> Vec<String> {
+        let samples = self.newest(width);
+        let levels = Chart::scale(&samples, height * 8);
+        let padding = " ".repeat(width - samples.len());
+        (0..height)
+            .map(|row| {
+                let below = (height - 1 - row) * 8;
+                let cells = levels.iter().map(|&level| match level.saturating_sub(below) {
+                    0 => ' ',
+                    eighths => LEVELS[eighths.min(8) - 1],
+                });
+                padding.chars().chain(cells).collect()
+            })
+            .collect()
+    }
+    // Rows of text, top first, with two samples per column
+    fn braille(&self, width: usize, height: usize) -> Vec<String> {
+        let samples = self.newest(width * 2);
+        let levels = Chart::scale(&samples, height * 4);
+        // The newest sample goes in the right of the last column
+        let padding = samples.len() % 2;
+        let mut levels: Vec<Option<usize>> = levels.into_iter().map(Some).collect();
+        levels.splice(0..0, std::iter::repeat_n(None, padding));
+        let columns = levels.len() / 2;
+        (0..height)
+            .map(|row| {
+                let below = (height - 1 - row) * 4;
+                let cells = levels.chunks(2).map(|pair| {
+                    let mut dots = 0;
+                    for (side, level) in pair.iter().enumerate() {
+                        let filled = level.map_or(0, |level| level.saturating_sub(below).min(4));
+                        dots |= DOTS[side][..filled].iter().fold(0, |dots, dot| dots | dot);
+                    }
+                    char::from_u32(0x2800 + dots).unwrap()
+                });
+                " ".repeat(width - columns).chars().chain(cells).collect()
+            })
+            .collect()
+    }
+    // Rows, one per sample from the top, with the value before the bar
+    fn bars(&self, width: usize, height: usize) -> Vec<Line> {
+        let samples = self.newest(height);
+        let values: Vec<String> = samples.iter().map(|&sample| format_value(sample)).collect();
+        let label = values.iter().map(|value| value.chars().count()).max().unwrap_or(0);
+        let room = width.saturating_sub(label + 1);
+        let max = samples.iter().copied().fold(0.0, f64::max);
+        (samples.iter().zip(values))
+            .map(|(&sample, value)| {
+                let eighths = match max > 0.0 {
+                    true => (sample.max(0.0) / max * (room * 8) as f64).round() as usize,
+                    false => 0,
+                };
+                let mut bar = "█".repeat(eighths / 8);
+                if eighths % 8 > 0 {
+                    bar.push(EIGHTHS[eighths % 8]);
+                }
+                Line::new().span(&format!("{:>label$} ", value), Style::default()).span(&bar, self.style)
+            })
+            .collect()
+    }
+    // Rows of the chart, at most `width` characters wide
+    pub fn render(&self, width: usize, height: usize) -> Vec<Line> {
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let rows = match self.kind {
+            ChartKind::Sparkline => self.sparkline(width, height),
+            ChartKind::Braille => self.braille(width, height),
+            ChartKind::Bars => return self.bars(width, height),
+        };
+        rows.into_iter().map(|row| Line::new().span(&row, self.style)).collect()
+    }
+}
+
+// Integers without decimals, and other numbers with two
+fn format_value(value: f64) -> String {
+    match value.fract() == 0.0 && value.abs() < 1e15 {
+        true => format!("{:.0}", value),
+        false => format!("{:.2}", value),
+    }
+}
@@ -0,0 +1,49 @@
+use super::line::Line;
+use regex::Regex;
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+    use crate::colors::Style;
+    fn line(text: &str) -> Line {
+        text.chars().map(|c| (c, Style::default())).collect()
+    }
+    #[test]
+    fn include_exclude() {
+        let mut filter = Filter::new(Some("error|warning"), Some("deprecated")).unwrap();
+        assert!(filter.allows(&line("error: missing file")));
+        assert!(!filter.allows(&line("compiling")));
+        assert!(!filter.allows(&line("warning: deprecated")));
+        filter.enabled = false;
+        assert!(filter.allows(&line("compiling")));
+        assert!(Filter::new(None, Some("[")).is_err());
+    }
+}
+
+// Lines displayed by a window. Lines that don't match are kept, but not displayed
+#[derive(Debug)]
+pub struct Filter {
+    // Only lines that match are displayed
+    include: Option<Regex>,
+    // Lines that match are not displayed
+    exclude: Option<Regex>,
+    pub enabled: bool,
+}
+
+impl Filter {
+    pub fn new(include: Option<&str>, exclude: Option<&str>) -> Result<Filter, regex::Error> {
+        Ok(Filter {
+            include: include.map(Regex::new).transpose()?,
+            exclude: exclude.map(Regex::new).transpose()?,
+            enabled: true,
+        })
+    }
+    pub fn allows(&self, line: &Line) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let text = line.text();
+        self.include.as_ref().is_none_or(|include| include.is_match(&text))
+            && !self.exclude.as_ref().is_some_and(|exclude| exclude.is_match(&text))
+    }
+}
@@ -0,0 +1,147 @@
+use crate::colors::{self, Style};
+use std::time::SystemTime;
+
+// A character of the screen with its style
+pub type Cell = (char, Style);
+
+#[cfg(test)]
+mod line_tests {
+    use super::*;
+    use crate::colors::Color;
+    #[test]
+    fn merge_spans() {
+        let red = Style { fg: Color::Red, ..Style::default() };
+        let mut line = Line::default();
+        for (c, style) in [('a', red), ('b', red), ('c', Style::default())] {
+            line.push(c, style);
+        }
+        assert_eq!(line.spans.len(), 2);
+        assert_eq!(line.len(), 3);
+        assert_eq!(line.cells()[1], ('b', red));
+    }
+    #[test]
+    fn ansi_text() {
+        let red = Style { fg: Color::Red, ..Style::default() };
+        let line: Line = [('a', Style::default()), ('b', red)].into_iter().collect();
+        let reset = colors::style_code(&Style::default());
+        assert_eq!(line.ansi(), format!("a{}b{}", colors::style_code(&red), reset));
+        assert_eq!(line.text(), "ab");
+    }
+}
+
+/// A `Span` is text with a single `Style`.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Span {
+    /// Create a `Span` of `text` with `style`.
+    pub fn new(text: &str, style: Style) -> Span {
+        Span {
+            text: text.to_string(),
+            style,
+        }
+    }
+}
+
+impl From<&str> for Span {
+    fn from(text: &str) -> Span {
+        Span::new(text, Style::default())
+    }
+}
+
+/// A `Line` is made of `Span`s with different styles, printed with `Screen::println_styled` or
+/// `Bridge::println_styled`.
+///
+/// ```ignore
+/// use multi_window_output::{Color, Line, Screen, Style};
+///
+/// let mut screen = Screen::new();
+/// let line = Line::new()
+///     .span("OK", Style { fg: Color::Green, bold: true, ..Style::default() })
+///     .span(" build finished ", Style::default())
+///     .span("1.2s", Style { dim: true, ..Style::default() });
+/// screen.println_styled(0, line).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Line {
+    pub spans: Vec<Span>,
+    // When the line arrived
+    pub(crate) time: Option<SystemTime>,
+}
+
+impl Line {
+    /// Create an empty `Line`.
+    pub fn new() -> Line {
+        Line::default()
+    }
+    /// Add `text` with `style` at the end of the line.
+    pub fn span(mut self, text: &str, style: Style) -> Self {
+        self.spans.push(Span::new(text, style));
+        self
+    }
+    /// Text of the line without styles.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+    // Append character, merging it with the last span if it has the same style
+    pub(crate) fn push(&mut self, c: char, style: Style) {
+        match self.spans.last_mut() {
+            Some(span) if span.style == style => span.text.push(c),
+            _ => self.spans.push(Span {
+                text: c.to_string(),
+                style,
+            }),
+        }
+    }
+    // Bytes of text
+    pub(crate) fn len(&self) -> usize {
+        self.spans.iter().map(|span| span.text.len()).sum()
+    }
+    // Text with the escape sequences of its styles, it ends with the default style
+    pub(crate) fn ansi(&self) -> String {
+        let mut text = String::new();
+        let mut style = Style::default();
+        for span in &self.spans {
+            if span.style != style {
+                text.push_str(&colors::style_code(&span.style));
+                style = span.style;
+            }
+            text.push_str(&span.text);
+        }
+        if style != Style::default() {
+            text.push_str(&colors::style_code(&Style::default()));
+        }
+        text
+    }
+    pub(crate) fn cells(&self) -> Vec<Cell> {
+        self.spans
+            .iter()
+            .flat_map(|span| span.text.chars().map(move |c| (c, span.style)))
+            .collect()
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Line {
+        Line { spans, time: None }
+    }
+}
+
+impl FromIterator<Span> for Line {
+    fn from_iter<I: IntoIterator<Item = Span>>(spans: I) -> Self {
+        Line::from(spans.into_iter().collect::<Vec<Span>>())
+    }
+}
+
+impl FromIterator<Cell> for Line {
+    fn from_iter<I: IntoIterator<Item = Cell>>(cells: I) -> Self {
+        let mut line = Line::default();
+        for (c, style) in cells {
+            line.push(c, style);
+        }
+        line
+    }
+}
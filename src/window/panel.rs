@@ -0,0 +1,130 @@
+use super::buffer::clean;
+use super::line::Line;
+use crate::colors::Style;
+
+#[cfg(test)]
+mod panel_tests {
+    use super::*;
+    use crate::colors::Color;
+    #[test]
+    fn aligned_sections() {
+        let red = Style { fg: Color::Red, ..Style::default() };
+        let mut panel = Panel::default();
+        panel.set("db/pool", "12", Style::default());
+        panel.set("uptime", "3m", Style::default());
+        panel.set("db/errors", "1", red);
+        panel.set("cache/hit rate", "98%", Style::default());
+        // Updated in place
+        panel.set("uptime", "4m", Style::default());
+        let lines = panel.render();
+        let text: Vec<String> = lines.iter().map(Line::text).collect();
+        assert_eq!(
+            text,
+            vec!["uptime      4m", "", "db", "  pool      12", "  errors    1", "", "cache", "  hit rate  98%"]
+        );
+        assert_eq!(lines[4].spans.last().unwrap().style, red);
+
+        panel.remove("db/pool");
+        panel.remove("db/errors");
+        assert_eq!(panel.render().len(), 4);
+    }
+    #[test]
+    fn control_characters() {
+        let mut panel = Panel::default();
+        panel.set("net/\x1b[31mstate", "a\x1b[31mb\nc", Style::default());
+        let text: Vec<String> = panel.render().iter().map(Line::text).collect();
+        assert_eq!(text, vec!["net", "  state  ab c"]);
+        panel.remove("net/state");
+        assert!(panel.render().is_empty());
+    }
+}
+
+// Space between the keys and the values
+const GAP: usize = 2;
+// Indentation of the keys of a section
+const INDENT: usize = 2;
+
+// Value of a key, in the section before the last `/` of the key
+#[derive(Clone, Debug)]
+struct Entry {
+    section: String,
+    key: String,
+    value: String,
+    style: Style,
+}
+
+// Values by key, grouped into sections
+#[derive(Debug, Default)]
+pub struct Panel {
+    // In the order they were added
+    entries: Vec<Entry>,
+}
+
+// Section and name of `key`
+fn split(key: &str) -> (&str, &str) {
+    key.rsplit_once('/').unwrap_or(("", key))
+}
+
+impl Panel {
+    // Replace the value of `key`, or add it at the end of its section. Control characters and escape
+    // sequences are removed from both
+    pub fn set(&mut self, key: &str, value: &str, style: Style) {
+        let (key, value) = (clean(key), clean(value));
+        let (section, key) = split(&key);
+        match self.entries.iter_mut().find(|entry| entry.section == section && entry.key == key) {
+            Some(entry) => {
+                entry.value = value;
+                entry.style = style;
+            }
+            None => self.entries.push(Entry {
+                section: section.to_string(),
+                key: key.to_string(),
+                value,
+                style,
+            }),
+        }
+    }
+    pub fn remove(&mut self, key: &str) {
+        let key = clean(key);
+        let (section, key) = split(&key);
+        self.entries.retain(|entry| entry.section != section || entry.key != key);
+    }
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+    // Keys without section first, then every section under its name in the order they appeared
+    pub fn render(&self) -> Vec<Line> {
+        let mut sections: Vec<&str> = vec![""];
+        for entry in &self.entries {
+            if !sections.contains(&entry.section.as_str()) {
+                sections.push(&entry.section);
+            }
+        }
+        let indent = |section: &str| if section.is_empty() { 0 } else { INDENT };
+        let width = (self.entries.iter())
+            .map(|entry| indent(&entry.section) + entry.key.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = vec![];
+        for section in sections {
+            let entries: Vec<&Entry> = self.entries.iter().filter(|entry| entry.section == section).collect();
+            if entries.is_empty() {
+                continue;
+            }
+            if !section.is_empty() {
+                // Blank line between sections
+                if !lines.is_empty() {
+                    lines.push(Line::new());
+                }
+                lines.push(Line::new().span(section, Style { bold: true, ..Style::default() }));
+            }
+            for entry in entries {
+                let key = format!("{}{}", " ".repeat(indent(section)), entry.key);
+                let key = format!("{:<width$}{}", key, " ".repeat(GAP), width = width);
+                lines.push(Line::new().span(&key, Style::default()).span(&entry.value, entry.style));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,200 @@
+use super::buffer::clean;
+use super::line::Line;
+use crate::colors::{Color, Style};
+use std::time::SystemTime;
+
+#[cfg(test)]
+mod progress_tests {
+    use super::*;
+    use std::time::Duration;
+    #[test]
+    fn render_bars() {
+        let start = SystemTime::UNIX_EPOCH;
+        let mut bar = ProgressBar::new("copy").total(100).unit("MB");
+        bar.set(0, start);
+        bar.set(45, start + Duration::from_secs(9));
+        let now = start + Duration::from_secs(10);
+        assert_eq!(bar.render(60, now).text(), "copy [█████████           ] 45/100 MB  45% 4.5 MB/s ETA 0:12");
+        assert_eq!(bar.render(61, now).text(), "copy [█████████▍           ] 45/100 MB  45% 4.5 MB/s ETA 0:12");
+        let ascii = bar.clone().ascii(true);
+        assert_eq!(ascii.render(50, now).text(), "copy [####------] 45/100 MB  45% 4.5 MB/s ETA 0:12");
+        // The bar is left out when the window is narrow
+        assert_eq!(bar.render(20, now).text(), "copy 45/100 MB  45% ");
+    }
+    #[test]
+    fn unknown_total() {
+        let start = SystemTime::UNIX_EPOCH;
+        let mut bar = ProgressBar::new("requests");
+        bar.set(10, start);
+        assert_eq!(bar.render(40, start).text(), "requests 10");
+        bar.set(30, start + Duration::from_secs(4));
+        assert_eq!(bar.render(40, start + Duration::from_secs(4)).text(), "requests 30 5.0/s");
+        // Going back starts measuring again
+        bar.set(5, start + Duration::from_secs(5));
+        assert_eq!(bar.render(40, start + Duration::from_secs(5)).text(), "requests 5");
+    }
+    #[test]
+    fn control_characters() {
+        let mut bar = ProgressBar::new("a\x1b[31mb\nc").unit("M\rB");
+        bar.set(3, SystemTime::UNIX_EPOCH);
+        assert_eq!(bar.render(40, SystemTime::UNIX_EPOCH).text(), "ab c 3 M B");
+    }
+}
+
+// Characters of a bar cell filled by 0 to 7 eighths from the left
+pub const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// A `ProgressBar` takes a row of a window, set with `Screen::set_progress_bar`. It displays its
+/// label, a bar, the current value out of the total, the percent done, the throughput and the
+/// estimated time left. Update it with `Screen::set_progress` or `Bridge::set_progress`.
+///
+/// ```ignore
+/// use multi_window_output::{ProgressBar, Screen};
+///
+/// let mut screen = Screen::new();
+/// // download [████████▌        ] 52/100 MB  52% 4.1 MB/s ETA 0:12
+/// screen.set_progress_bar(0, 0, ProgressBar::new("download").total(100).unit("MB")).unwrap();
+/// screen.set_progress(0, 0, 52).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProgressBar {
+    label: String,
+    total: Option<u64>,
+    unit: String,
+    ascii: bool,
+    style: Style,
+    value: u64,
+    // First value measured and when it was set, for the throughput
+    start: Option<(SystemTime, u64)>,
+}
+
+impl ProgressBar {
+    /// Create a `ProgressBar` with `label`. Without a total, only the value and the throughput are displayed.
+    pub fn new(label: &str) -> ProgressBar {
+        ProgressBar {
+            label: clean(label),
+            total: None,
+            unit: String::new(),
+            ascii: false,
+            style: Style { fg: Color::Green, ..Style::default() },
+            value: 0,
+            start: None,
+        }
+    }
+    /// Value at which the task is done.
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+    /// Unit displayed after the value and the throughput, e.g. `MB`.
+    pub fn unit(mut self, unit: &str) -> Self {
+        self.unit = clean(unit);
+        self
+    }
+    /// Draw the bar with `#` and `-` instead of Unicode block characters, for terminals without them.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+    /// Style of the filled part of the bar. Green by default.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    // Update the value at time `now`
+    pub fn set(&mut self, value: u64, now: SystemTime) {
+        match self.start {
+            Some((_, first)) if value >= first => {}
+            _ => self.start = Some((now, value)),
+        }
+        self.value = value;
+    }
+    // Units per second since the first value, if it can be measured
+    fn rate(&self, now: SystemTime) -> Option<f64> {
+        let (time, first) = self.start?;
+        let elapsed = now.duration_since(time).unwrap_or_default().as_secs_f64();
+        match elapsed > 0.0 && self.value > first {
+            true => Some((self.value - first) as f64 / elapsed),
+            false => None,
+        }
+    }
+    // Fraction of the total done
+    fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| match total {
+            0 => 1.0,
+            total => (self.value as f64 / total as f64).min(1.0),
+        })
+    }
+    // Value, percent, throughput and estimated time left
+    fn stats(&self, now: SystemTime) -> String {
+        let unit = match self.unit.as_str() {
+            "" => String::new(),
+            unit => format!(" {}", unit),
+        };
+        let mut stats = match self.total {
+            Some(total) => format!("{}/{}{}", self.value, total, unit),
+            None => format!("{}{}", self.value, unit),
+        };
+        if let Some(fraction) = self.fraction() {
+            stats.push_str(&format!(" {:>3}%", (fraction * 100.0) as u64));
+        }
+        if let Some(rate) = self.rate(now) {
+            stats.push_str(&format!(" {:.1}{}/s", rate, unit));
+            if let Some(total) = self.total {
+                let left = total.saturating_sub(self.value) as f64 / rate;
+                stats.push_str(&format!(" ETA {}", format_duration(left as u64)));
+            }
+        }
+        stats
+    }
+    // Cells of a bar `width` characters wide, including the brackets
+    fn bar(&self, fraction: f64, width: usize) -> Line {
+        let inner = width - 2;
+        let (filled, partial, empty) = match self.ascii {
+            true => {
+                let filled = (fraction * inner as f64) as usize;
+                ("#".repeat(filled), String::new(), "-".repeat(inner - filled))
+            }
+            false => {
+                let eighths = (fraction * (inner * 8) as f64) as usize;
+                let (full, rest) = (eighths / 8, eighths % 8);
+                let partial = match (rest, full < inner) {
+                    (0, _) | (_, false) => String::new(),
+                    (rest, true) => EIGHTHS[rest].to_string(),
+                };
+                let empty = " ".repeat(inner - full - partial.chars().count());
+                ("█".repeat(full), partial, empty)
+            }
+        };
+        Line::new()
+            .span("[", Style::default())
+            .span(&filled, self.style)
+            .span(&partial, self.style)
+            .span(&empty, Style { dim: true, ..Style::default() })
+            .span("]", Style::default())
+    }
+    // Row displaying the bar, at most `width` characters wide
+    pub fn render(&self, width: usize, now: SystemTime) -> Line {
+        let stats = self.stats(now);
+        let mut line = Line::new();
+        if !self.label.is_empty() {
+            line = line.span(&self.label, Style { bold: true, ..Style::default() }).span(" ", Style::default());
+        }
+        // The bar takes the room left, if it is enough to be useful
+        let room = width.saturating_sub(line.cells().len() + stats.chars().count() + 1);
+        if let (Some(fraction), true) = (self.fraction(), room >= 4) {
+            let bar = self.bar(fraction, room);
+            line.spans.extend(bar.spans);
+            line = line.span(" ", Style::default());
+        }
+        line.span(&stats, Style::default()).cells().into_iter().take(width).collect()
+    }
+}
+
+// Seconds as `m:ss`, or `h:mm:ss` from an hour
+fn format_duration(secs: u64) -> String {
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{}:{:02}:{:02}", hours, secs / 60 % 60, secs % 60),
+    }
+}
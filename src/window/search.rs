@@ -0,0 +1,84 @@
+use super::line::{Cell, Line};
+use crate::colors::Style;
+use regex::Regex;
+
+#[cfg(test)]
+mod search_tests {
+    use super::*;
+    fn line(text: &str) -> Line {
+        text.chars().map(|c| (c, Style::default())).collect()
+    }
+    #[test]
+    fn plain_text() {
+        let style = Style { reverse: true, ..Style::default() };
+        let search = Search::new(&Pattern::Text("a.b".to_string()), style).unwrap();
+        assert!(search.is_match(&line("xa.by")));
+        assert!(!search.is_match(&line("axb")));
+        let cells = search.highlight(&line("ñ a.b"));
+        let styled: Vec<bool> = cells.iter().map(|cell| cell.1 == style).collect();
+        assert_eq!(styled, vec![false, false, true, true, true]);
+    }
+    #[test]
+    fn regex() {
+        let search = Search::new(&Pattern::Regex("err(or)?".to_string()), Style::default()).unwrap();
+        assert!(search.is_match(&line("an error")));
+        assert!(!search.is_match(&line("ok")));
+        // Patterns that only match empty text don't match any line
+        let empty = Search::new(&Pattern::Regex("x*".to_string()), Style::default()).unwrap();
+        assert!(!empty.is_match(&line("ok")));
+        assert!(Search::new(&Pattern::Regex("(".to_string()), Style::default()).is_err());
+    }
+}
+
+/// Text searched with `Screen::search`.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Pattern};
+///
+/// let mut screen = Screen::new();
+/// // Lines containing "panic"
+/// screen.search(0, Pattern::Text("panic".to_string())).unwrap();
+/// // Lines with an HTTP error status
+/// screen.search(0, Pattern::Regex(r"status=[45]\d\d".to_string())).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    /// Exact text.
+    Text(String),
+    /// Regular expression, with the syntax of the `regex` crate.
+    Regex(String),
+}
+
+// Search active in a window
+#[derive(Debug)]
+pub struct Search {
+    regex: Regex,
+    // Style of the matches
+    style: Style,
+}
+
+impl Search {
+    pub fn new(pattern: &Pattern, style: Style) -> Result<Search, regex::Error> {
+        let regex = match pattern {
+            Pattern::Text(text) => Regex::new(&regex::escape(text))?,
+            Pattern::Regex(regex) => Regex::new(regex)?,
+        };
+        Ok(Search { regex, style })
+    }
+    pub fn is_match(&self, line: &Line) -> bool {
+        self.regex.find_iter(&line.text()).any(|m| !m.is_empty())
+    }
+    // Cells of `line`, with the style of the matches
+    pub fn highlight(&self, line: &Line) -> Vec<Cell> {
+        let text = line.text();
+        let mut cells = line.cells();
+        let mut matches = self.regex.find_iter(&text).peekable();
+        for (cell, (index, _)) in cells.iter_mut().zip(text.char_indices()) {
+            while matches.next_if(|m| m.end() <= index).is_some() {}
+            if matches.peek().is_some_and(|m| m.start() <= index) {
+                cell.1 = self.style;
+            }
+        }
+        cells
+    }
+}
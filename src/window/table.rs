@@ -0,0 +1,254 @@
+use super::buffer::clean;
+use super::line::Line;
+use crate::colors::Style;
+use std::cmp::Ordering;
+
+#[cfg(test)]
+mod table_tests {
+    use super::*;
+    fn jobs() -> Table {
+        let mut table = Table::new(vec![
+            Column::new("job"),
+            Column::new("state").align(Align::Center),
+            Column::new("time").align(Align::Right),
+        ]);
+        table.set_row("1", &["build", "done", "12.5"]);
+        table.set_row("2", &["test", "running", "3"]);
+        table.set_row("3", &["deploy", "queued", "0"]);
+        table
+    }
+    fn text(lines: Vec<Line>) -> Vec<String> {
+        lines.iter().map(Line::text).collect()
+    }
+    #[test]
+    fn auto_widths() {
+        let mut table = jobs();
+        table.set_row("2", &["test", "failed", "4.25"]);
+        assert_eq!(
+            text(table.render(usize::MAX)),
+            vec!["job     state   time", "build    done   12.5", "test    failed  4.25", "deploy  queued     0"]
+        );
+        // The widest columns are truncated first
+        assert_eq!(text(table.render(16))[2], "test  fai…  4.25");
+    }
+    #[test]
+    fn sort_rows() {
+        let mut table = jobs();
+        table.sort(Some(2), false);
+        let keys: Vec<&str> = table.sorted().iter().map(|row| row.0.as_str()).collect();
+        assert_eq!(keys, vec!["3", "2", "1"]);
+        assert_eq!(text(table.render(usize::MAX))[0], "job      state   time ▲");
+        table.sort(Some(0), true);
+        let keys: Vec<&str> = table.sorted().iter().map(|row| row.0.as_str()).collect();
+        assert_eq!(keys, vec!["2", "3", "1"]);
+        table.remove_row("2");
+        assert_eq!(table.rows.len(), 2);
+    }
+    #[test]
+    fn sort_mixed_values() {
+        let mut table = Table::new(vec![Column::new("value")]);
+        let values = ["9", "10", "1a", "b", "-2.5", "10", "a1", "0x1"];
+        // Enough rows for the sort to check the order is total
+        for i in 0..64 {
+            table.set_row(&i.to_string(), &[values[i * 7 % values.len()]]);
+        }
+        table.sort(Some(0), false);
+        let mut sorted: Vec<&str> = table.sorted().iter().map(|row| row.1[0].as_str()).collect();
+        sorted.dedup();
+        assert_eq!(sorted, vec!["-2.5", "9", "10", "0x1", "1a", "a1", "b"]);
+    }
+    #[test]
+    fn control_characters() {
+        let mut table = Table::new(vec![Column::new("a\nb"), Column::new("c")]);
+        table.set_row("1", &["a\x1b[31mb\nc", "d\r"]);
+        assert_eq!(text(table.render(usize::MAX)), vec!["a b   c", "ab c  d"]);
+    }
+}
+
+/// Alignment of the values of a table `Column`.
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// A `Column` of a table, set with `Screen::set_table`. Columns are as wide as their widest value
+/// unless their width is set. When the window is too narrow, the widest columns are truncated.
+///
+/// ```ignore
+/// use multi_window_output::{Align, Column, Screen};
+///
+/// let mut screen = Screen::new();
+/// screen.set_table(0, vec![
+///     Column::new("job"),
+///     Column::new("state").width(8),
+///     Column::new("time").align(Align::Right),
+/// ]).unwrap();
+/// screen.set_row(0, "42", &["build", "running", "1.2s"]).unwrap();
+/// // Updated in place
+/// screen.set_row(0, "42", &["build", "done", "3.4s"]).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Column {
+    title: String,
+    align: Align,
+    width: Option<usize>,
+}
+
+impl Column {
+    /// Create a left aligned `Column` with `title` in the header.
+    pub fn new(title: &str) -> Column {
+        Column {
+            title: clean(title),
+            align: Align::Left,
+            width: None,
+        }
+    }
+    /// Align the values and the title of the column.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+    /// Make the column `width` characters wide instead of as wide as its values.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+}
+
+// Space between columns
+const GAP: usize = 2;
+
+// Rows under a header, keyed to be updated in place
+#[derive(Debug, Default)]
+pub struct Table {
+    columns: Vec<Column>,
+    // Rows in the order they were added
+    rows: Vec<(String, Vec<String>)>,
+    // Column the rows are sorted by, and if they go in descending order
+    sort: Option<(usize, bool)>,
+}
+
+// Compare values as numbers if both are, with numbers before the other values
+fn compare(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.total_cmp(&y).then_with(|| a.cmp(b)),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+// Fit `text` in `width` characters, with an ellipsis if it is cut
+fn fit(text: &str, width: usize, align: Align) -> String {
+    let length = text.chars().count();
+    if length > width {
+        return match width {
+            0 => String::new(),
+            width => text.chars().take(width - 1).chain(['…']).collect(),
+        };
+    }
+    let space = width - length;
+    let (left, right) = match align {
+        Align::Left => (0, space),
+        Align::Right => (space, 0),
+        Align::Center => (space / 2, space - space / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Table {
+        Table {
+            columns,
+            ..Table::default()
+        }
+    }
+    // Replace the values of the row with `key`, or add it at the end. Control characters and escape
+    // sequences are removed from the values
+    pub fn set_row(&mut self, key: &str, values: &[&str]) {
+        let values: Vec<String> = values.iter().map(|value| clean(value)).collect();
+        // Rows with more values than columns get untitled ones
+        while self.columns.len() < values.len() {
+            self.columns.push(Column::new(""));
+        }
+        match self.rows.iter_mut().find(|row| row.0 == key) {
+            Some(row) => row.1 = values,
+            None => self.rows.push((key.to_string(), values)),
+        }
+    }
+    pub fn remove_row(&mut self, key: &str) {
+        self.rows.retain(|row| row.0 != key);
+    }
+    // Remove the rows, keeping the columns
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+    pub fn sort(&mut self, column: Option<usize>, descending: bool) {
+        self.sort = column.map(|column| (column, descending));
+    }
+    // Rows in the order they are displayed
+    fn sorted(&self) -> Vec<&(String, Vec<String>)> {
+        let mut rows: Vec<&(String, Vec<String>)> = self.rows.iter().collect();
+        if let Some((column, descending)) = self.sort {
+            let value = |row: &(String, Vec<String>)| row.1.get(column).cloned().unwrap_or_default();
+            rows.sort_by(|a, b| match descending {
+                false => compare(&value(a), &value(b)),
+                true => compare(&value(b), &value(a)),
+            });
+        }
+        rows
+    }
+    // Titles of the columns, with an arrow in the one rows are sorted by
+    fn titles(&self) -> Vec<String> {
+        (self.columns.iter().enumerate())
+            .map(|(i, column)| match self.sort {
+                Some((sorted, descending)) if sorted == i => {
+                    format!("{} {}", column.title, if descending { '▼' } else { '▲' })
+                }
+                _ => column.title.clone(),
+            })
+            .collect()
+    }
+    // Width of every column, narrowing the widest ones until the table fits in `width`
+    fn widths(&self, titles: &[String], width: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = (self.columns.iter().enumerate())
+            .map(|(i, column)| {
+                column.width.unwrap_or_else(|| {
+                    let values = self.rows.iter().filter_map(|row| row.1.get(i));
+                    values.chain([&titles[i]]).map(|value| value.chars().count()).max().unwrap_or(0)
+                })
+            })
+            .collect();
+        let gaps = GAP * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + gaps > width {
+            let widest = widths.iter_mut().max().unwrap();
+            if *widest <= 1 {
+                break;
+            }
+            *widest -= 1;
+        }
+        widths
+    }
+    // Header and rows, at most `width` characters wide
+    pub fn render(&self, width: usize) -> Vec<Line> {
+        let titles = self.titles();
+        let widths = self.widths(&titles, width);
+        let row = |values: &[String], style: Style| -> Line {
+            let cells = (self.columns.iter().zip(&widths).enumerate()).map(|(i, (column, &width))| {
+                fit(values.get(i).map(String::as_str).unwrap_or(""), width, column.align)
+            });
+            let text = cells.collect::<Vec<String>>().join(&" ".repeat(GAP));
+            Line::new().span(text.trim_end(), style)
+        };
+        let mut lines = vec![];
+        // Tables without titles have no header
+        if titles.iter().any(|title| !title.is_empty()) {
+            lines.push(row(&titles, Style { bold: true, underline: true, ..Style::default() }));
+        }
+        lines.extend(self.sorted().into_iter().map(|(_, values)| row(values, Style::default())));
+        lines
+    }
+}
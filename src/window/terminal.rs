@@ -0,0 +1,375 @@
+use super::ansi::{self, Action, Csi, Parser};
+use super::line::Cell;
+use crate::colors::Style;
+
+#[cfg(test)]
+mod terminal_tests {
+    use super::*;
+    fn screen(terminal: &Terminal) -> Vec<String> {
+        terminal
+            .rows()
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.0).collect::<String>().trim_end().to_string())
+            .collect()
+    }
+    #[test]
+    fn cursor_movement() {
+        let mut terminal = Terminal::new(10, 3);
+        terminal.feed("hello\r\nworld\x1b[1;3HX\x1b[2;1H\x1b[K!");
+        assert_eq!(screen(&terminal), vec!["heXlo", "!", ""]);
+        terminal.feed("\x1b[2J\x1b[3;8Habcdef");
+        // Writing past the last column continues in the next line
+        assert_eq!(screen(&terminal), vec!["", "       abc", "def"]);
+    }
+    #[test]
+    fn scrolling() {
+        let mut terminal = Terminal::new(5, 3);
+        terminal.feed("1\r\n2\r\n3\r\n4");
+        assert_eq!(screen(&terminal), vec!["2", "3", "4"]);
+        // Only the scroll region moves
+        terminal.feed("\x1b[2;3r\x1b[3;1H\n5");
+        assert_eq!(screen(&terminal), vec!["2", "4", "5"]);
+        terminal.feed("\x1b[2;1H\x1bM6");
+        assert_eq!(screen(&terminal), vec!["2", "6", "4"]);
+    }
+    #[test]
+    fn alternate_screen() {
+        let mut terminal = Terminal::new(5, 2);
+        terminal.feed("main");
+        terminal.feed("\x1b[?1049h\x1b[Htop");
+        assert_eq!(screen(&terminal), vec!["top", ""]);
+        terminal.feed("\x1b[?1049l");
+        assert_eq!(screen(&terminal), vec!["main", ""]);
+    }
+    #[test]
+    fn resize() {
+        let mut terminal = Terminal::new(4, 3);
+        terminal.feed("1\r\n2\r\n3");
+        terminal.resize(2, 2);
+        assert_eq!(screen(&terminal), vec!["2", "3"]);
+        assert_eq!(terminal.size(), (2, 2));
+    }
+    #[test]
+    fn clear_screen() {
+        let mut terminal = Terminal::new(4, 2);
+        terminal.feed("ab\r\ncd");
+        terminal.clear();
+        terminal.feed("e");
+        assert_eq!(screen(&terminal), vec!["e", ""]);
+    }
+}
+
+// Cursor position and style saved by `ESC 7`
+#[derive(Clone, Copy, Debug, Default)]
+struct Saved {
+    row: usize,
+    col: usize,
+    style: Style,
+}
+
+// VT100/xterm terminal emulator. It keeps the screen of a program as a grid of cells
+#[derive(Debug)]
+pub struct Terminal {
+    width: usize,
+    height: usize,
+    grid: Vec<Vec<Cell>>,
+    // Main screen while the alternate screen is in use
+    main: Option<Vec<Vec<Cell>>>,
+    row: usize,
+    col: usize,
+    // Last column was written, the next character goes to the next line
+    wrap_pending: bool,
+    style: Style,
+    saved: Saved,
+    // Scroll region, inclusive
+    top: usize,
+    bottom: usize,
+    autowrap: bool,
+    cursor_visible: bool,
+    parser: Parser,
+}
+
+impl Terminal {
+    pub fn new(width: usize, height: usize) -> Terminal {
+        let (width, height) = (width.max(1), height.max(1));
+        Terminal {
+            width,
+            height,
+            grid: vec![vec![(' ', Style::default()); width]; height],
+            main: None,
+            row: 0,
+            col: 0,
+            wrap_pending: false,
+            style: Style::default(),
+            saved: Saved::default(),
+            top: 0,
+            bottom: height - 1,
+            autowrap: true,
+            cursor_visible: true,
+            parser: Parser::new(),
+        }
+    }
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+    // Rows of the screen, without the cursor
+    pub fn cells(&self) -> Vec<Vec<Cell>> {
+        self.grid.clone()
+    }
+    // Rows of the screen, with the cursor displayed in reverse
+    pub fn rows(&self) -> Vec<Vec<Cell>> {
+        let mut rows = self.grid.clone();
+        if self.cursor_visible {
+            let cell = &mut rows[self.row][self.col];
+            cell.1.reverse = !cell.1.reverse;
+        }
+        rows
+    }
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let (width, height) = (width.max(1), height.max(1));
+        // Drop rows from the top so the cursor stays in the screen
+        let drop = (self.row + 1).saturating_sub(height);
+        for grid in std::iter::once(&mut self.grid).chain(self.main.as_mut()) {
+            grid.drain(..drop.min(grid.len()));
+            grid.resize(height, vec![]);
+            for row in grid.iter_mut() {
+                row.resize(width, (' ', Style::default()));
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.row -= drop;
+        self.col = self.col.min(width - 1);
+        self.top = 0;
+        self.bottom = height - 1;
+        self.wrap_pending = false;
+    }
+    // Blank the screen and move the cursor to the top left corner, like `ESC [2J ESC [H`
+    pub fn clear(&mut self) {
+        self.grid = vec![vec![(' ', Style::default()); self.width]; self.height];
+        self.goto(0, 0);
+    }
+    pub fn feed(&mut self, text: &str) {
+        for c in text.chars() {
+            match self.parser.advance(c) {
+                Some(Action::Print(c)) => self.print(c),
+                Some(Action::Control(c)) => self.control(c),
+                Some(Action::Csi(csi)) => self.csi(&csi),
+                Some(Action::Esc { intermediate: None, action }) => self.esc(action),
+                // Character sets and other sequences are not supported
+                _ => {}
+            }
+        }
+    }
+    // Empty cell with the current background
+    fn blank(&self) -> Cell {
+        let style = Style {
+            bg: self.style.bg,
+            ..Style::default()
+        };
+        (' ', style)
+    }
+    fn print(&mut self, c: char) {
+        if self.wrap_pending {
+            self.col = 0;
+            self.linefeed();
+        }
+        self.grid[self.row][self.col] = (c, self.style);
+        if self.col + 1 < self.width {
+            self.col += 1;
+        } else {
+            self.wrap_pending = self.autowrap;
+        }
+    }
+    fn control(&mut self, c: char) {
+        match c {
+            '\r' => self.goto(self.row, 0),
+            '\n' | '\x0b' | '\x0c' => self.linefeed(),
+            '\x08' => self.goto(self.row, self.col.saturating_sub(1)),
+            '\t' => self.goto(self.row, (self.col / 8 + 1) * 8),
+            _ => {}
+        }
+    }
+    fn esc(&mut self, action: char) {
+        match action {
+            '7' => self.save(),
+            '8' => self.restore(),
+            'D' => self.linefeed(),
+            'E' => {
+                self.goto(self.row, 0);
+                self.linefeed();
+            }
+            'M' => self.reverse_index(),
+            'c' => *self = Terminal::new(self.width, self.height),
+            _ => {}
+        }
+    }
+    fn csi(&mut self, csi: &Csi) {
+        let n = csi.param(0, 1) as usize;
+        match (csi.private, csi.action) {
+            (None, 'A') => self.goto(self.row.saturating_sub(n), self.col),
+            (None, 'B') => self.goto(self.row + n, self.col),
+            (None, 'C') => self.goto(self.row, self.col + n),
+            (None, 'D') => self.goto(self.row, self.col.saturating_sub(n)),
+            (None, 'E') => self.goto(self.row + n, 0),
+            (None, 'F') => self.goto(self.row.saturating_sub(n), 0),
+            (None, 'G' | '`') => self.goto(self.row, n - 1),
+            (None, 'd') => self.goto(n - 1, self.col),
+            (None, 'H' | 'f') => self.goto(n - 1, csi.param(1, 1) as usize - 1),
+            (None, 'J') => self.erase_display(csi.params.first().copied().unwrap_or(0)),
+            (None, 'K') => self.erase_line(csi.params.first().copied().unwrap_or(0)),
+            (None, 'L') => self.insert_lines(n),
+            (None, 'M') => self.delete_lines(n),
+            (None, '@') => {
+                let blank = self.blank();
+                let row = &mut self.grid[self.row];
+                for _ in 0..n.min(self.width - self.col) {
+                    row.insert(self.col, blank);
+                    row.pop();
+                }
+            }
+            (None, 'P') => {
+                let blank = self.blank();
+                let row = &mut self.grid[self.row];
+                for _ in 0..n.min(self.width - self.col) {
+                    row.remove(self.col);
+                    row.push(blank);
+                }
+            }
+            (None, 'X') => {
+                let blank = self.blank();
+                let end = (self.col + n).min(self.width);
+                self.grid[self.row][self.col..end].fill(blank);
+            }
+            (None, 'S') => self.scroll_up(self.top, n),
+            (None, 'T') => self.scroll_down(self.top, n),
+            (None, 'r') => {
+                let top = csi.param(0, 1) as usize - 1;
+                let bottom = (csi.param(1, self.height as u16) as usize).min(self.height) - 1;
+                if top < bottom {
+                    self.top = top;
+                    self.bottom = bottom;
+                    self.goto(0, 0);
+                }
+            }
+            (None, 'm') => ansi::apply_sgr(&mut self.style, &csi.params),
+            (None, 's') => self.save(),
+            (None, 'u') => self.restore(),
+            (Some('?'), 'h' | 'l') => {
+                let set = csi.action == 'h';
+                for mode in &csi.params {
+                    self.set_mode(*mode, set);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn set_mode(&mut self, mode: u16, set: bool) {
+        match mode {
+            7 => self.autowrap = set,
+            25 => self.cursor_visible = set,
+            47 | 1047 | 1049 => {
+                if mode == 1049 && set {
+                    self.save();
+                }
+                let blank = vec![vec![(' ', Style::default()); self.width]; self.height];
+                match (set, self.main.take()) {
+                    // Switch to the alternate screen
+                    (true, None) => self.main = Some(std::mem::replace(&mut self.grid, blank)),
+                    // Go back to the main screen
+                    (false, Some(main)) => self.grid = main,
+                    (_, main) => self.main = main,
+                }
+                if mode == 1049 && !set {
+                    self.restore();
+                }
+            }
+            _ => {}
+        }
+    }
+    fn goto(&mut self, row: usize, col: usize) {
+        self.row = row.min(self.height - 1);
+        self.col = col.min(self.width - 1);
+        self.wrap_pending = false;
+    }
+    fn save(&mut self) {
+        self.saved = Saved {
+            row: self.row,
+            col: self.col,
+            style: self.style,
+        };
+    }
+    fn restore(&mut self) {
+        self.style = self.saved.style;
+        self.goto(self.saved.row, self.saved.col);
+    }
+    // Move down, scrolling if the cursor is at the bottom of the scroll region
+    fn linefeed(&mut self) {
+        self.wrap_pending = false;
+        if self.row == self.bottom {
+            self.scroll_up(self.top, 1);
+        } else if self.row + 1 < self.height {
+            self.row += 1;
+        }
+    }
+    // Move up, scrolling if the cursor is at the top of the scroll region
+    fn reverse_index(&mut self) {
+        self.wrap_pending = false;
+        if self.row == self.top {
+            self.scroll_down(self.top, 1);
+        } else if self.row > 0 {
+            self.row -= 1;
+        }
+    }
+    // Move rows from `from` to the bottom of the scroll region up
+    fn scroll_up(&mut self, from: usize, n: usize) {
+        let blank = vec![self.blank(); self.width];
+        for _ in 0..n.min(self.bottom + 1 - from) {
+            self.grid.remove(from);
+            self.grid.insert(self.bottom, blank.clone());
+        }
+    }
+    // Move rows from `from` to the bottom of the scroll region down
+    fn scroll_down(&mut self, from: usize, n: usize) {
+        let blank = vec![self.blank(); self.width];
+        for _ in 0..n.min(self.bottom + 1 - from) {
+            self.grid.remove(self.bottom);
+            self.grid.insert(from, blank.clone());
+        }
+    }
+    fn insert_lines(&mut self, n: usize) {
+        if (self.top..=self.bottom).contains(&self.row) {
+            self.scroll_down(self.row, n);
+            self.goto(self.row, 0);
+        }
+    }
+    fn delete_lines(&mut self, n: usize) {
+        if (self.top..=self.bottom).contains(&self.row) {
+            self.scroll_up(self.row, n);
+            self.goto(self.row, 0);
+        }
+    }
+    fn erase_display(&mut self, mode: u16) {
+        let blank = self.blank();
+        let rows = match mode {
+            0 => self.row + 1..self.height,
+            1 => 0..self.row,
+            _ => 0..self.height,
+        };
+        for row in rows {
+            self.grid[row].fill(blank);
+        }
+        if mode < 2 {
+            self.erase_line(mode);
+        }
+    }
+    fn erase_line(&mut self, mode: u16) {
+        let blank = self.blank();
+        let cols = match mode {
+            0 => self.col..self.width,
+            1 => 0..self.col + 1,
+            _ => 0..self.width,
+        };
+        self.grid[self.row][cols].fill(blank);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::time;
+use std::time::SystemTime;
+
+#[cfg(test)]
+mod timestamps_tests {
+    use super::*;
+    use std::time::Duration;
+    #[test]
+    fn prefixes() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let time = start + Duration::from_millis(12_345);
+        let previous = start + Duration::from_millis(12_000);
+        assert_eq!(Timestamps::Off.prefix(time, None, start), None);
+        assert_eq!(Timestamps::Relative.prefix(time, None, start).unwrap(), "   12.345");
+        assert_eq!(Timestamps::Delta.prefix(time, Some(previous), start).unwrap(), "   +0.345");
+        assert_eq!(Timestamps::Delta.prefix(time, None, start).unwrap(), "   +0.000");
+        let clock = Timestamps::Clock("%H:%M:%S".to_string()).prefix(time, None, start).unwrap();
+        assert_eq!(clock, time::format_clock(time));
+    }
+}
+
+/// Time displayed before every line of a window, set with `Screen::set_timestamps`. Lines record
+/// when they arrived even if it is not displayed.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Timestamps};
+///
+/// let mut screen = Screen::new();
+///
+/// // 14:03:27 Compiling...
+/// screen.set_timestamps(0, Timestamps::Clock("%H:%M:%S".to_string())).unwrap();
+///
+/// //     1.250 Compiling...
+/// screen.set_timestamps(0, Timestamps::Relative).unwrap();
+///
+/// //    +0.016 Compiling...
+/// screen.set_timestamps(0, Timestamps::Delta).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Debug, Default)]
+pub enum Timestamps {
+    /// Don't display the time.
+    #[default]
+    Off,
+    /// Local time, formatted with the `strftime` syntax of the C library, e.g. `%H:%M:%S`.
+    Clock(String),
+    /// Seconds since the `Screen` was created.
+    Relative,
+    /// Seconds since the previous line displayed in the window.
+    Delta,
+}
+
+impl Timestamps {
+    // Text displayed before a line that arrived at `time`
+    pub fn prefix(&self, time: SystemTime, previous: Option<SystemTime>, start: SystemTime) -> Option<String> {
+        let since = |earlier: SystemTime| time.duration_since(earlier).unwrap_or_default().as_secs_f64();
+        match self {
+            Timestamps::Off => None,
+            Timestamps::Clock(format) => Some(time::format_time(time, format)),
+            Timestamps::Relative => Some(format!("{:>9.3}", since(start))),
+            Timestamps::Delta => {
+                let delta = previous.map_or(0.0, since);
+                Some(format!("{:>9}", format!("+{:.3}", delta)))
+            }
+        }
+    }
+}
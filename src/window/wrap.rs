@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod wrap_tests {
+    use super::*;
+    fn rows(line: &str, width: usize, mode: Wrap) -> Vec<String> {
+        let line: Vec<char> = line.chars().collect();
+        wrap(&line, width, mode, |c| *c == ' ', ' ')
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect()
+    }
+    #[test]
+    fn truncate() {
+        assert_eq!(rows("Hello World", 5, Wrap::Truncate), vec!["Hello World"]);
+    }
+    #[test]
+    fn char_wrap() {
+        assert_eq!(rows("Hello World", 5, Wrap::Char), vec!["Hello", " Worl", "d"]);
+        assert_eq!(rows("", 5, Wrap::Char), vec![""]);
+    }
+    #[test]
+    fn word_wrap() {
+        assert_eq!(
+            rows("error: expected one of", 10, Wrap::Word { indent: 0 }),
+            vec!["error:", "expected", "one of"]
+        );
+        assert_eq!(
+            rows("error: expected one of", 10, Wrap::Word { indent: 2 }),
+            vec!["error:", "  expected", "  one of"]
+        );
+        // Words longer than the row are broken
+        assert_eq!(
+            rows("abcdefghijkl", 5, Wrap::Word { indent: 1 }),
+            vec!["abcde", " fghi", " jkl"]
+        );
+    }
+}
+
+/// `Wrap` sets what a window does with lines longer than its width.
+///
+/// ```ignore
+/// use multi_window_output::{Screen, Wrap};
+///
+/// let mut screen = Screen::new();
+///
+/// // Break long lines between words, and indent the rows after the first one
+/// screen.set_wrap(0, Wrap::Word { indent: 4 }).unwrap();
+/// ```
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub enum Wrap {
+    /// Cut lines at the window's width.
+    #[default]
+    Truncate,
+    /// Continue lines in the next row at the character that doesn't fit.
+    Char,
+    /// Continue lines in the next row at the last word that fits. The rows after the first one are
+    /// indented by `indent` spaces.
+    Word { indent: usize },
+}
+
+// Split `line` into rows of at most `width` cells, except in `Wrap::Truncate` mode
+pub fn wrap<T: Clone>(
+    line: &[T],
+    width: usize,
+    mode: Wrap,
+    is_space: impl Fn(&T) -> bool,
+    blank: T,
+) -> Vec<Vec<T>> {
+    let width = width.max(1);
+    let indent = match mode {
+        Wrap::Truncate => return vec![line.to_vec()],
+        Wrap::Char if line.is_empty() => return vec![vec![]],
+        Wrap::Char => return line.chunks(width).map(<[T]>::to_vec).collect(),
+        Wrap::Word { indent } if indent < width => indent,
+        Wrap::Word { .. } => 0,
+    };
+    let mut rows = vec![];
+    let mut pos = 0;
+    loop {
+        // Rows after the first one start with the indent
+        let (mut row, available) = match rows.is_empty() {
+            true => (vec![], width),
+            false => (vec![blank.clone(); indent], width - indent),
+        };
+        // Rest of the line fits
+        if line.len() - pos <= available {
+            row.extend_from_slice(&line[pos..]);
+            rows.push(row);
+            return rows;
+        }
+        // Break at the last space that fits, or break the word if there is none
+        let end = match (pos + 1..=pos + available).rev().find(|&i| is_space(&line[i])) {
+            Some(space) => space,
+            None => pos + available,
+        };
+        row.extend_from_slice(&line[pos..end]);
+        rows.push(row);
+        pos = end;
+        while pos < line.len() && is_space(&line[pos]) {
+            pos += 1;
+        }
+        if pos == line.len() {
+            return rows;
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::utf8::Decoder;
+use crate::{Bridge, Screen};
+
+#[cfg(test)]
+#[allow(clippy::items_after_test_module)]
+mod writer_tests {
+    use super::*;
+    use crate::Cmds;
+    use std::io::Write as _;
+    // Write through `std::fmt::Write`
+    fn sum(writer: &mut impl std::fmt::Write) {
+        write!(writer, "{} + {}", 1, 2).unwrap();
+    }
+    #[test]
+    fn screen_writer() {
+        let mut screen = Screen::new();
+        let mut writer = screen.writer(0).unwrap();
+        let bytes = "niño\n".as_bytes();
+        writer.write_all(&bytes[..3]).unwrap();
+        writer.write_all(&bytes[3..]).unwrap();
+        sum(&mut writer);
+        writeln!(writer, " = {}", 3).unwrap();
+        write!(writer, "partial").unwrap();
+        drop(writer);
+
+        let window = screen.windows[0].as_ref().unwrap();
+        let lines: Vec<String> = window.buffer.iter().map(|line| line.text()).collect();
+        assert_eq!(lines, vec!["niño", "1 + 2 = 3"]);
+        assert_eq!(window.buffer.pending().unwrap().text(), "partial");
+        assert!(screen.writer(1).is_err());
+    }
+    #[test]
+    fn incomplete_character() {
+        let mut screen = Screen::new();
+        let mut writer = screen.writer(0).unwrap();
+        writer.write_all(&"añ".as_bytes()[..2]).unwrap();
+        drop(writer);
+        assert_eq!(screen.windows[0].as_ref().unwrap().buffer.pending().unwrap().text(), "a\u{fffd}");
+    }
+    #[test]
+    fn bridge_writer() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let bridge = Bridge { bridge: tx, hash: [0].into() };
+        let mut writer = bridge.writer(0).unwrap();
+        write!(writer, "a\nb").unwrap();
+        writer.flush().unwrap();
+        crate::wprintln!(bridge, 0, "c{}", 1).unwrap();
+        let messages: Vec<String> = rx
+            .try_iter()
+            .filter_map(|(cmd, _, text)| match cmd {
+                Cmds::Println => Some(format!("println {}", text)),
+                Cmds::Print => Some(format!("print {}", text)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages, vec!["println a", "print b", "println c1"]);
+    }
+}
+
+/// Print a formatted line in window `id` of a `Screen` or a `Bridge`, like `println!`. Returns
+/// `()` if successful, `Err(std::io::ErrorKind)` if not.
+///
+/// ```ignore
+/// use multi_window_output::{wprintln, Screen};
+///
+/// let mut screen = Screen::new();
+/// wprintln!(screen, 0, "{} of {} done", 3, 10).unwrap();
+/// ```
+#[macro_export]
+macro_rules! wprintln {
+    ($target:expr, $id:expr) => {
+        $target.println($id, "")
+    };
+    ($target:expr, $id:expr, $($arg:tt)*) => {
+        $target.println($id, &format!($($arg)*))
+    };
+}
+
+/// Print formatted text in window `id` of a `Screen` or a `Bridge` without ending the line, like
+/// `print!`. Returns `()` if successful, `Err(std::io::ErrorKind)` if not.
+#[macro_export]
+macro_rules! wprint {
+    ($target:expr, $id:expr, $($arg:tt)*) => {
+        $target.print($id, &format!($($arg)*))
+    };
+}
+
+// Where a `WindowWriter` prints
+pub(crate) enum Target<'a> {
+    Screen(&'a mut Screen),
+    Bridge(Bridge),
+}
+
+/// A `WindowWriter` prints in a window of a `Screen` or a `Bridge` through `std::fmt::Write` and
+/// `std::io::Write`, so it can be passed to code that expects a writer. Get one with
+/// `Screen::writer` or `Bridge::writer`.
+///
+/// Text is printed one line at a time, once its new line character is written. Flushing the
+/// writer, or dropping it, prints the unfinished line without ending it. UTF-8 characters split
+/// between writes of bytes are joined back, and one left incomplete when the writer is dropped
+/// is printed as `U+FFFD`.
+///
+/// ```ignore
+/// use multi_window_output::{Bridge, Screen};
+/// use std::io::Write;
+///
+/// let bridge = Bridge::new(Screen::new());
+/// let mut writer = bridge.writer(0).unwrap();
+/// writeln!(writer, "{} requests", 12).unwrap();
+/// serde_json::to_writer_pretty(&mut writer, &vec![1, 2, 3]).unwrap();
+/// writer.flush().unwrap();
+/// ```
+pub struct WindowWriter<'a> {
+    target: Target<'a>,
+    id: usize,
+    decoder: Decoder,
+    // Text of the unfinished line
+    pending: String,
+}
+
+impl<'a> WindowWriter<'a> {
+    pub(crate) fn new(target: Target<'a>, id: usize) -> WindowWriter<'a> {
+        WindowWriter {
+            target,
+            id,
+            decoder: Decoder::new(),
+            pending: String::new(),
+        }
+    }
+    // Add text, and print the lines it finishes
+    fn push(&mut self, text: &str) -> Result<(), std::io::ErrorKind> {
+        self.pending.push_str(text);
+        while let Some(end) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=end).collect();
+            let line = line.trim_end_matches('\n');
+            match &mut self.target {
+                Target::Screen(screen) => screen.println(self.id, line)?,
+                Target::Bridge(bridge) => bridge.println(self.id, line)?,
+            }
+        }
+        Ok(())
+    }
+    // Print the unfinished line
+    fn print_pending(&mut self) -> Result<(), std::io::ErrorKind> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let text = std::mem::take(&mut self.pending);
+        match &mut self.target {
+            Target::Screen(screen) => screen.print(self.id, &text),
+            Target::Bridge(bridge) => bridge.print(self.id, &text),
+        }
+    }
+}
+
+impl std::fmt::Write for WindowWriter<'_> {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        self.push(text).map_err(|_| std::fmt::Error)
+    }
+}
+
+impl std::io::Write for WindowWriter<'_> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        let text = self.decoder.decode(bytes);
+        self.push(&text)?;
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(self.print_pending()?)
+    }
+}
+
+impl Drop for WindowWriter<'_> {
+    fn drop(&mut self) {
+        // An incomplete character is printed as `U+FFFD` instead of being lost
+        let rest = self.decoder.finish();
+        let _ = self.push(&rest);
+        let _ = self.print_pending();
+    }
+}